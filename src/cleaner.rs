@@ -1,7 +1,8 @@
 use std::sync::Arc;
 use anyhow::Result;
-use markdown::{mdast::Node, ParseOptions, to_mdast};
+use markdown::{mdast::{AlignKind, Node}, ParseOptions, to_mdast};
 use crate::processors::{NodeProcessor, ProcessContext};
+use crate::report::ProcessReport;
 
 /// AI-specialized PDF markdown cleaner
 /// Designed to clean up markdown content extracted from PDFs by AI tools
@@ -10,6 +11,12 @@ pub struct MarkdownCleaner {
     processors: Vec<Arc<dyn NodeProcessor>>,
 }
 
+impl Default for MarkdownCleaner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MarkdownCleaner {
     pub fn new() -> Self {
         Self {
@@ -22,16 +29,36 @@ impl MarkdownCleaner {
     }
 
     pub fn clean(&self, markdown_text: &str) -> Result<String> {
+        self.clean_with_report(markdown_text).map(|(cleaned, _)| cleaned)
+    }
+
+    /// Clean the markdown and return the processing report collected along the way
+    pub fn clean_with_report(&self, markdown_text: &str) -> Result<(String, ProcessReport)> {
+        self.clean_with_context(markdown_text, ProcessContext::default())
+    }
+
+    /// Clean the markdown using a caller-provided context (e.g. with `file_path` set)
+    pub fn clean_with_context(&self, markdown_text: &str, context: ProcessContext) -> Result<(String, ProcessReport)> {
+        let (root, report) = self.process_tree(markdown_text, context)?;
+        Ok((self.render(&root), report))
+    }
+
+    /// Parse and process the markdown, returning the cleaned tree instead of text
+    /// (for callers that extract data from it, such as table export)
+    pub fn process_tree(&self, markdown_text: &str, mut context: ProcessContext) -> Result<(Node, ProcessReport)> {
+        context.source.get_or_insert_with(|| markdown_text.to_string());
         let parse_options = ParseOptions::default();
         let mut root = to_mdast(markdown_text, &parse_options)
             .map_err(|e| anyhow::anyhow!("Failed to parse markdown: {}", e))?;
 
-        let context = ProcessContext::default();
         self.process_node_recursively(&mut root, &context)?;
+        Ok((root, context.report.into_inner()))
+    }
 
-        // Convert back to markdown using the same library's serializer
+    /// Serialize a (processed) tree back to markdown
+    pub fn render(&self, root: &Node) -> String {
         // Since to_markdown is not available, we need to implement our own serialization
-        Ok(self.node_to_markdown(&root))
+        self.node_to_markdown(root)
     }
 
     fn process_node_recursively(&self, node: &mut Node, context: &ProcessContext) -> Result<()> {
@@ -125,9 +152,12 @@ impl MarkdownCleaner {
                     .collect::<Vec<_>>()
                     .join("")
             }
-            Node::Image(image) => {
-                format!("![{}]({})", image.alt, image.url)
-            }
+            Node::Image(image) => match &image.title {
+                Some(title) => format!("![{}]({} \"{}\")", image.alt, image.url, title.replace('"', "\\\"")),
+                None => format!("![{}]({})", image.alt, image.url),
+            },
+            Node::InlineCode(code) => format!("`{}`", code.value),
+            Node::Html(html) => html.value.clone(),
             Node::Code(code) => {
                 if let Some(lang) = &code.lang {
                     format!("```{}\n{}\n```", lang, code.value)
@@ -146,23 +176,43 @@ impl MarkdownCleaner {
                     .join("\n")
             }
             Node::List(list) => {
+                let start = list.start.unwrap_or(1) as usize;
                 list.children.iter()
-                    .map(|child| self.node_to_markdown(child))
-                    .collect::<Vec<_>>()
-                    .join("\n")
-            }
-            Node::ListItem(item) => {
-                let content = item.children.iter()
-                    .map(|child| self.node_to_markdown(child))
+                    .enumerate()
+                    .map(|(index, child)| {
+                        let marker = if list.ordered {
+                            format!("{}.", start + index)
+                        } else {
+                            "-".to_string()
+                        };
+                        self.list_item_to_markdown(child, &marker)
+                    })
                     .collect::<Vec<_>>()
-                    .join("");
-                format!("- {}", content)
+                    .join(if list.spread { "\n\n" } else { "\n" })
             }
+            Node::ListItem(_) => self.list_item_to_markdown(node, "-"),
             Node::Table(table) => {
-                table.children.iter()
+                let mut rows = table.children.iter()
                     .map(|child| self.node_to_markdown(child))
-                    .collect::<Vec<_>>()
-                    .join("\n")
+                    .collect::<Vec<_>>();
+
+                // GFM requires a delimiter row after the header
+                if !rows.is_empty() {
+                    let columns = match &table.children[0] {
+                        Node::TableRow(row) => row.children.len(),
+                        _ => 0,
+                    };
+                    let delimiters = (0..columns)
+                        .map(|index| match table.align.get(index) {
+                            Some(AlignKind::Left) => ":---",
+                            Some(AlignKind::Right) => "---:",
+                            Some(AlignKind::Center) => ":---:",
+                            _ => "---",
+                        })
+                        .collect::<Vec<_>>();
+                    rows.insert(1, format!("| {} |", delimiters.join(" | ")));
+                }
+                rows.join("\n")
             }
             Node::TableRow(row) => {
                 let cells = row.children.iter()
@@ -175,8 +225,37 @@ impl MarkdownCleaner {
                     .map(|child| self.node_to_markdown(child))
                     .collect::<Vec<_>>()
                     .join("")
+                    .replace('\n', " ")
+                    .replace('|', "\\|")
             }
             _ => String::new(),
         }
     }
+
+    /// Render a list item with the given marker, indenting continuation lines
+    /// (wrapped text, nested lists) to the item's content column
+    fn list_item_to_markdown(&self, node: &Node, marker: &str) -> String {
+        let content = match node {
+            Node::ListItem(item) => item.children.iter()
+                .map(|child| self.node_to_markdown(child))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            other => self.node_to_markdown(other),
+        };
+
+        let indent = " ".repeat(marker.chars().count() + 1);
+        content.lines()
+            .enumerate()
+            .map(|(index, line)| {
+                if index == 0 {
+                    format!("{} {}", marker, line)
+                } else if line.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}{}", indent, line)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
\ No newline at end of file
@@ -0,0 +1,307 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use markdown::mdast::{Image, Node};
+use markdown::unist::Position;
+use crate::processors::figure_caption_processor::pair_captions;
+use crate::processors::paragraph_lines::inline_text;
+use crate::processors::table_caption_processor::is_caption;
+use crate::processors::table_processor::pipe_paragraph_to_table;
+
+/// File format for exported tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    Csv,
+    Tsv,
+    Json,
+}
+
+impl TableFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TableFormat::Csv => "csv",
+            TableFormat::Tsv => "tsv",
+            TableFormat::Json => "json",
+        }
+    }
+
+    /// Render a table's rows (header first) in this format
+    pub fn render(&self, table: &TableExport) -> String {
+        match self {
+            TableFormat::Csv => table.rows.iter()
+                .map(|row| row.iter().map(|cell| csv_field(cell)).collect::<Vec<_>>().join(","))
+                .map(|line| line + "\r\n")
+                .collect(),
+            TableFormat::Tsv => table.rows.iter()
+                .map(|row| row.iter().map(|cell| tsv_field(cell)).collect::<Vec<_>>().join("\t"))
+                .map(|line| line + "\n")
+                .collect(),
+            TableFormat::Json => {
+                let header = table.rows.first().map(|row| json_array(row)).unwrap_or_else(|| "[]".to_string());
+                let rows = table.rows.iter().skip(1).map(|row| format!("    {}", json_array(row))).collect::<Vec<_>>();
+                let rows = if rows.is_empty() { "[]".to_string() } else { format!("[\n{}\n  ]", rows.join(",\n")) };
+                format!(
+                    "{{\n  \"caption\": {},\n  \"header\": {},\n  \"rows\": {}\n}}\n",
+                    json_optional(table.caption.as_deref()),
+                    header,
+                    rows,
+                )
+            }
+        }
+    }
+}
+
+/// A table pulled out of a cleaned document as plain cell text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableExport {
+    /// 1-based position of the table in the document
+    pub index: usize,
+    /// Caption paragraph directly above the table (`表1 ...`, `Table 3: ...`)
+    pub caption: Option<String>,
+    /// Where the table came from in the source, when known
+    pub position: Option<Position>,
+    /// Cell texts, header row first
+    pub rows: Vec<Vec<String>>,
+}
+
+fn csv_field(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+fn tsv_field(cell: &str) -> String {
+    cell.replace(['\t', '\n', '\r'], " ")
+}
+
+pub(crate) fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+pub(crate) fn json_optional(value: Option<&str>) -> String {
+    value.map(json_string).unwrap_or_else(|| "null".to_string())
+}
+
+fn json_array(values: &[String]) -> String {
+    format!("[{}]", values.iter().map(|value| json_string(value)).collect::<Vec<_>>().join(", "))
+}
+
+fn table_rows(node: &Node) -> Option<Vec<Vec<String>>> {
+    let table = match node {
+        Node::Table(table) => table.clone(),
+        Node::Paragraph(paragraph) => pipe_paragraph_to_table(paragraph)?,
+        _ => return None,
+    };
+    Some(table.children.iter()
+        .map(|row| row.children()
+            .map(|cells| cells.iter().map(|cell| inline_text(cell).trim().to_string()).collect())
+            .unwrap_or_default())
+        .collect())
+}
+
+fn collect_tables(children: &[Node], tables: &mut Vec<TableExport>) {
+    for (index, child) in children.iter().enumerate() {
+        if let Some(rows) = table_rows(child) {
+            let caption = index.checked_sub(1)
+                .map(|previous| &children[previous])
+                .filter(|previous| is_caption(previous))
+                .map(|previous| inline_text(previous).trim().to_string());
+            tables.push(TableExport {
+                index: tables.len() + 1,
+                caption,
+                position: child.position().cloned(),
+                rows,
+            });
+        } else if let Some(grandchildren) = child.children() {
+            collect_tables(grandchildren, tables);
+        }
+    }
+}
+
+/// Extract every table of a cleaned tree: `Node::Table`s and pipe tables kept as text
+pub fn extract_tables(root: &Node) -> Vec<TableExport> {
+    let mut tables = Vec::new();
+    if let Some(children) = root.children() {
+        collect_tables(children, &mut tables);
+    }
+    tables
+}
+
+/// Sidecar file path for the `index`-th table: `<base>.table-<index>.<ext>`
+pub fn table_export_path(base: &Path, index: usize, format: TableFormat) -> PathBuf {
+    let mut path = base.as_os_str().to_owned();
+    path.push(format!(".table-{}.{}", index, format.extension()));
+    PathBuf::from(path)
+}
+
+/// Manifest path for a base path: `<base>.tables.json`
+pub fn table_manifest_path(base: &Path) -> PathBuf {
+    let mut path = base.as_os_str().to_owned();
+    path.push(".tables.json");
+    PathBuf::from(path)
+}
+
+/// Manifest listing each exported file (relative to the manifest) with its caption and source position
+pub fn table_manifest(base: &Path, tables: &[TableExport], format: TableFormat) -> String {
+    let entries = tables.iter()
+        .map(|table| {
+            let (line, column) = table.position.as_ref()
+                .map(|position| (position.start.line.to_string(), position.start.column.to_string()))
+                .unwrap_or_else(|| ("null".to_string(), "null".to_string()));
+            format!(
+                "    {{\"index\": {}, \"file\": {}, \"caption\": {}, \"line\": {}, \"column\": {}, \"rows\": {}, \"columns\": {}}}",
+                table.index,
+                json_string(&table_export_path(base, table.index, format)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default()),
+                json_optional(table.caption.as_deref()),
+                line,
+                column,
+                table.rows.len(),
+                table.rows.first().map(|row| row.len()).unwrap_or(0),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if entries.is_empty() {
+        "{\n  \"tables\": []\n}\n".to_string()
+    } else {
+        format!("{{\n  \"tables\": [\n{}\n  ]\n}}\n", entries.join(",\n"))
+    }
+}
+
+/// Write each table to `<base>.table-N.<ext>` and the manifest to `<base>.tables.json`,
+/// returning the paths written (manifest last)
+pub fn write_table_exports(base: &Path, tables: &[TableExport], format: TableFormat) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for table in tables {
+        let path = table_export_path(base, table.index, format);
+        fs::write(&path, format.render(table))?;
+        written.push(path);
+    }
+    let manifest = table_manifest_path(base);
+    fs::write(&manifest, table_manifest(base, tables, format))?;
+    written.push(manifest);
+    Ok(written)
+}
+
+/// An image of a cleaned document with the context needed to describe or OCR it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageExport {
+    /// 1-based position of the image in the document
+    pub index: usize,
+    pub url: String,
+    pub alt: String,
+    pub title: Option<String>,
+    /// Where the image came from in the source, when known
+    pub position: Option<Position>,
+    /// Figure caption next to the image (`図1 ...`, `Figure 2: ...`)
+    pub caption: Option<String>,
+    /// Texts of the enclosing headings, outermost first
+    pub headings: Vec<String>,
+}
+
+/// Images anywhere inside a node: in headings, table cells, emphasis, links and the like
+fn inline_images<'a>(node: &'a Node, images: &mut Vec<&'a Image>) {
+    match node {
+        Node::Image(image) => images.push(image),
+        other => {
+            for child in other.children().into_iter().flatten() {
+                inline_images(child, images);
+            }
+        }
+    }
+}
+
+fn collect_images(children: &[Node], headings: &mut Vec<(u8, String)>, images: &mut Vec<ImageExport>) {
+    let captions = pair_captions(children);
+    for (index, child) in children.iter().enumerate() {
+        if let Node::Heading(heading) = child {
+            headings.retain(|(depth, _)| *depth < heading.depth);
+            let text: String = heading.children.iter().filter(|inline| !matches!(inline, Node::Image(_))).map(inline_text).collect();
+            headings.push((heading.depth, text.trim().to_string()));
+        }
+        // Containers of blocks track headings and captions at their own level
+        if matches!(child, Node::Blockquote(_) | Node::List(_) | Node::ListItem(_) | Node::FootnoteDefinition(_)) {
+            if let Some(grandchildren) = child.children() {
+                collect_images(grandchildren, headings, images);
+            }
+            continue;
+        }
+
+        let mut found = Vec::new();
+        inline_images(child, &mut found);
+        let caption = captions[index].as_ref().map(|(_, caption)| caption.clone());
+        for image in found {
+            images.push(ImageExport {
+                index: images.len() + 1,
+                url: image.url.clone(),
+                alt: image.alt.clone(),
+                title: image.title.clone(),
+                position: image.position.clone(),
+                caption: caption.clone(),
+                headings: headings.iter().map(|(_, text)| text.clone()).collect(),
+            });
+        }
+    }
+}
+
+/// Extract every image of a cleaned tree with its caption and heading path
+pub fn extract_images(root: &Node) -> Vec<ImageExport> {
+    let mut images = Vec::new();
+    if let Some(children) = root.children() {
+        collect_images(children, &mut Vec::new(), &mut images);
+    }
+    images
+}
+
+/// JSON manifest listing each image with its alt, URL, title, source position, caption and heading path
+pub fn image_manifest(images: &[ImageExport]) -> String {
+    let entries = images.iter()
+        .map(|image| {
+            let (line, column) = image.position.as_ref()
+                .map(|position| (position.start.line.to_string(), position.start.column.to_string()))
+                .unwrap_or_else(|| ("null".to_string(), "null".to_string()));
+            format!(
+                "    {{\"index\": {}, \"url\": {}, \"alt\": {}, \"title\": {}, \"line\": {}, \"column\": {}, \"caption\": {}, \"headings\": {}}}",
+                image.index,
+                json_string(&image.url),
+                json_string(&image.alt),
+                json_optional(image.title.as_deref()),
+                line,
+                column,
+                json_optional(image.caption.as_deref()),
+                json_array(&image.headings),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if entries.is_empty() {
+        "{\n  \"images\": []\n}\n".to_string()
+    } else {
+        format!("{{\n  \"images\": [\n{}\n  ]\n}}\n", entries.join(",\n"))
+    }
+}
+
+/// Write the image manifest to `path`
+pub fn write_image_manifest(path: &Path, images: &[ImageExport]) -> Result<()> {
+    fs::write(path, image_manifest(images))?;
+    Ok(())
+}
\ No newline at end of file
@@ -1,5 +1,18 @@
 pub mod processors;
 pub mod cleaner;
+pub mod report;
+pub mod export;
 
-pub use processors::{NodeProcessor, ProcessContext, WhitespaceProcessor, ImageProcessor, TableProcessor};
-pub use cleaner::MarkdownCleaner;
\ No newline at end of file
+pub use processors::{NodeProcessor, ProcessContext, WhitespaceProcessor, CharClass, LanguagePreset, ImageProcessor, TableProcessor, LigatureProcessor, NormalizationProcessor, NormalizationPolicy,
+                     InvisibleCharProcessor, InvisibleCategory, InvisibleAction,
+                     BulletListProcessor, NumberedListProcessor, Enumerator, EnumeratorKind,
+                     AlignedTableProcessor, TableStitchProcessor, TableCaptionProcessor,
+                     HtmlTableProcessor, TableLinearizeProcessor, TableLinearization,
+                     ImageAssetProcessor, ImageCheckProcessor, DataUriImageProcessor,
+                     RepeatedImageProcessor, FigureCaptionProcessor, CjkSpacingProcessor, SpacingPolicy,
+                     PunctuationProcessor, PunctuationStyle, RubyProcessor, RubyOutput,
+                     VerticalTextProcessor};
+pub use cleaner::MarkdownCleaner;
+pub use report::{ProcessReport, ReportEntry};
+pub use export::{TableExport, TableFormat, extract_tables, write_table_exports,
+                 ImageExport, extract_images, write_image_manifest};
\ No newline at end of file
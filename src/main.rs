@@ -1,58 +1,382 @@
 use std::env;
 use std::fs;
 use std::io::{self, Read};
+use std::path::Path;
 use std::sync::Arc;
-use anyhow::Result;
-use mdclean::{MarkdownCleaner, WhitespaceProcessor, ImageProcessor, TableProcessor};
+use anyhow::{bail, Result};
+use mdclean::{MarkdownCleaner, WhitespaceProcessor, LanguagePreset, ImageProcessor, TableProcessor, LigatureProcessor, NormalizationProcessor, NormalizationPolicy, InvisibleCharProcessor,
+             BulletListProcessor, NumberedListProcessor, AlignedTableProcessor,
+             TableStitchProcessor, TableCaptionProcessor, HtmlTableProcessor,
+             TableLinearizeProcessor, TableLinearization, ImageAssetProcessor, ImageCheckProcessor,
+             DataUriImageProcessor, RepeatedImageProcessor, FigureCaptionProcessor, CjkSpacingProcessor, SpacingPolicy,
+             PunctuationProcessor, PunctuationStyle, RubyProcessor, RubyOutput,
+             VerticalTextProcessor, ProcessContext, TableFormat, extract_tables, write_table_exports,
+             extract_images, write_image_manifest};
+
+/// Command line options (flags may appear anywhere among the positional arguments)
+#[derive(Debug, Default)]
+struct CliOptions {
+    input: Option<String>,
+    output: Option<String>,
+    report: bool,
+    language: Option<LanguagePreset>,
+    keep_indent: bool,
+    cjk_spacing: Option<SpacingPolicy>,
+    punctuation: Option<PunctuationStyle>,
+    ruby: Option<RubyOutput>,
+    vertical_text: bool,
+    normalize: Vec<NormalizationPolicy>,
+    bullet_lists: bool,
+    numbered_lists: bool,
+    preserve_markers: bool,
+    aligned_tables: bool,
+    repair_tables: bool,
+    stitch_tables: bool,
+    table_captions: bool,
+    html_tables: bool,
+    export_tables: Option<TableFormat>,
+    linearize_tables: Option<TableLinearization>,
+    normalize_images: bool,
+    rebase_images: bool,
+    asset_prefix: Option<String>,
+    assets_dir: Option<String>,
+    check_images: bool,
+    strict_images: bool,
+    extract_data_uris: Option<String>,
+    remove_repeated_images: bool,
+    repeated_image_threshold: Option<usize>,
+    allowed_images: Vec<String>,
+    figure_captions: bool,
+    fill_alt: bool,
+    image_manifest: Option<String>,
+}
+
+fn print_usage(program: &str) {
+    eprintln!("Usage:");
+    eprintln!("  {} [options] [input_file] [output_file]", program);
+    eprintln!("  {} [options] [input_file]                (output to stdout)", program);
+    eprintln!("  {} [options]                             (stdin to stdout)", program);
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --report                Print the processing report to stderr");
+    eprintln!("  --language <lang>       Scripts whose inner spaces are removed: ja (default), zh, ko");
+    eprintln!("  --keep-indent           Keep one ideographic space (　) of paragraph and line indentation");
+    eprintln!("  --cjk-spacing <policy>  Space between CJK and Latin text: insert, remove, preserve");
+    eprintln!("  --punctuation <style>   Japanese punctuation house style: jis, academic, halfwidth-parens");
+    eprintln!("  --ruby <form>           Handle furigana artifacts: strip, html (<ruby>), parens (漢字（かんじ）)");
+    eprintln!("  --vertical-text         Reassemble vertical (tategaki) text extracted a character or two per line");
+    eprintln!("  --normalize <policies>  Unicode normalization, comma separated:");
+    eprintln!("                          nfc, nfkc, fullwidth-ascii, halfwidth-kana");
+    eprintln!("  --bullet-lists          Convert glyph bullets (•, ●, ・, ...) into lists");
+    eprintln!("  --numbered-lists        Convert enumerated lines (1), (1), ①, 一、, a.) into lists");
+    eprintln!("  --preserve-markers      Keep the original enumerators of converted numbered lists");
+    eprintln!("  --aligned-tables        Convert whitespace-aligned columns into tables");
+    eprintln!("  --repair-tables         Repair pipe tables without delimiter row or with ragged rows");
+    eprintln!("  --stitch-tables         Merge tables split across page breaks");
+    eprintln!("  --table-captions        Attach captions (表1, Table 3:) to tables and move them above");
+    eprintln!("  --html-tables           Convert simple HTML <table> blocks into markdown tables");
+    eprintln!("  --export-tables <fmt>   Write each table to <output>.table-N.<fmt> (csv, tsv, json)");
+    eprintln!("                          with a <output>.tables.json manifest");
+    eprintln!("  --linearize-tables <form>  Rewrite tables for LLM ingestion:");
+    eprintln!("                          key-value, tsv, sentence");
+    eprintln!("  --normalize-images      Trim image alt text and repair image URLs (spaces, backslashes, %)");
+    eprintln!("  --rebase-images         Rewrite relative image URLs relative to the output file");
+    eprintln!("  --asset-prefix <prefix> Rewrite relative image URLs under this prefix (e.g. a CDN URL)");
+    eprintln!("  --assets-dir <dir>      Copy referenced local images into this directory, linked from the output");
+    eprintln!("  --check-images          Report local images missing on disk");
+    eprintln!("  --strict-images         Like --check-images, but fail when an image is missing");
+    eprintln!("  --extract-data-uris <dir>  Decode data: URI images into files in this directory");
+    eprintln!("  --remove-repeated-images   Remove decorative images repeated on every page (keeps the first)");
+    eprintln!("  --repeated-image-threshold <n>  Images occurring more than n times are decorative (default 2)");
+    eprintln!("  --allow-image <pattern>    Never remove images whose URL contains the pattern (repeatable)");
+    eprintln!("  --figure-captions       Report figure captions (図1, Figure 2:) linked to adjacent images");
+    eprintln!("  --fill-alt              Like --figure-captions, and copy captions into empty image alt text");
+    eprintln!("  --image-manifest <file> Write every image with its alt, URL, caption and headings as JSON");
+}
+
+fn parse_normalization_policy(value: &str) -> Option<NormalizationPolicy> {
+    match value {
+        "nfc" => Some(NormalizationPolicy::Nfc),
+        "nfkc" => Some(NormalizationPolicy::Nfkc),
+        "fullwidth-ascii" => Some(NormalizationPolicy::FullwidthAsciiToHalfwidth),
+        "halfwidth-kana" => Some(NormalizationPolicy::HalfwidthKatakanaToFullwidth),
+        _ => None,
+    }
+}
+
+fn parse_language(value: &str) -> Option<LanguagePreset> {
+    match value {
+        "ja" => Some(LanguagePreset::Japanese),
+        "zh" => Some(LanguagePreset::Chinese),
+        "ko" => Some(LanguagePreset::Korean),
+        _ => None,
+    }
+}
+
+fn parse_spacing_policy(value: &str) -> Option<SpacingPolicy> {
+    match value {
+        "insert" => Some(SpacingPolicy::Insert),
+        "remove" => Some(SpacingPolicy::Remove),
+        "preserve" => Some(SpacingPolicy::Preserve),
+        _ => None,
+    }
+}
+
+fn parse_punctuation_style(value: &str) -> Option<PunctuationStyle> {
+    match value {
+        "jis" => Some(PunctuationStyle::Jis),
+        "academic" => Some(PunctuationStyle::Academic),
+        "halfwidth-parens" => Some(PunctuationStyle::HalfwidthParentheses),
+        _ => None,
+    }
+}
+
+fn parse_ruby_output(value: &str) -> Option<RubyOutput> {
+    match value {
+        "strip" => Some(RubyOutput::Strip),
+        "html" => Some(RubyOutput::Html),
+        "parens" => Some(RubyOutput::Parentheses),
+        _ => None,
+    }
+}
+
+fn parse_table_format(value: &str) -> Option<TableFormat> {
+    match value {
+        "csv" => Some(TableFormat::Csv),
+        "tsv" => Some(TableFormat::Tsv),
+        "json" => Some(TableFormat::Json),
+        _ => None,
+    }
+}
+
+fn parse_linearization(value: &str) -> Option<TableLinearization> {
+    match value {
+        "key-value" => Some(TableLinearization::KeyValue),
+        "tsv" => Some(TableLinearization::Tsv),
+        "sentence" => Some(TableLinearization::Sentence),
+        _ => None,
+    }
+}
+
+fn parse_args(args: &[String]) -> Option<CliOptions> {
+    let mut options = CliOptions::default();
+    let mut positional = Vec::new();
+
+    let mut args = args[1..].iter();
+
+    while let Some(arg) = args.next() {
+        // Accept both `--flag value` and `--flag=value`
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || inline_value.clone().or_else(|| args.next().cloned());
+
+        match flag {
+            "--report" => options.report = true,
+            "--language" => options.language = Some(parse_language(&value()?)?),
+            "--keep-indent" => options.keep_indent = true,
+            "--cjk-spacing" => options.cjk_spacing = Some(parse_spacing_policy(&value()?)?),
+            "--punctuation" => options.punctuation = Some(parse_punctuation_style(&value()?)?),
+            "--ruby" => options.ruby = Some(parse_ruby_output(&value()?)?),
+            "--vertical-text" => options.vertical_text = true,
+            "--bullet-lists" => options.bullet_lists = true,
+            "--numbered-lists" => options.numbered_lists = true,
+            "--preserve-markers" => options.preserve_markers = true,
+            "--aligned-tables" => options.aligned_tables = true,
+            "--repair-tables" => options.repair_tables = true,
+            "--stitch-tables" => options.stitch_tables = true,
+            "--table-captions" => options.table_captions = true,
+            "--html-tables" => options.html_tables = true,
+            "--normalize-images" => options.normalize_images = true,
+            "--rebase-images" => options.rebase_images = true,
+            "--asset-prefix" => options.asset_prefix = Some(value()?),
+            "--assets-dir" => options.assets_dir = Some(value()?),
+            "--check-images" => options.check_images = true,
+            "--strict-images" => options.strict_images = true,
+            "--extract-data-uris" => options.extract_data_uris = Some(value()?),
+            "--remove-repeated-images" => options.remove_repeated_images = true,
+            "--repeated-image-threshold" => options.repeated_image_threshold = Some(value()?.parse().ok()?),
+            "--allow-image" => options.allowed_images.push(value()?),
+            "--figure-captions" => options.figure_captions = true,
+            "--fill-alt" => options.fill_alt = true,
+            "--image-manifest" => options.image_manifest = Some(value()?),
+            "--export-tables" => options.export_tables = Some(parse_table_format(&value()?)?),
+            "--linearize-tables" => options.linearize_tables = Some(parse_linearization(&value()?)?),
+            "--normalize" => {
+                for policy in value()?.split(',') {
+                    options.normalize.push(parse_normalization_policy(policy.trim())?);
+                }
+            }
+            flag if flag.starts_with("--") => return None,
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    if positional.len() > 2 {
+        return None;
+    }
+    let mut positional = positional.into_iter();
+    options.input = positional.next();
+    options.output = positional.next();
+    Some(options)
+}
 
 fn main() -> Result<()> {
     let args: Vec<String> = env::args().collect();
 
-    let input_content = match args.len() {
-        1 => {
+    let options = match parse_args(&args) {
+        Some(options) => options,
+        None => {
+            print_usage(&args[0]);
+            std::process::exit(1);
+        }
+    };
+
+    let input_content = match &options.input {
+        // Read from file
+        Some(path) => fs::read_to_string(path)?,
+        None => {
             // Read from stdin
             let mut buffer = String::new();
             io::stdin().read_to_string(&mut buffer)?;
             buffer
         }
-        2 => {
-            // Read from file, output to stdout
-            fs::read_to_string(&args[1])?
-        }
-        3 => {
-            // Read from file, output to file
-            fs::read_to_string(&args[1])?
-        }
-        _ => {
-            eprintln!("Usage:");
-            eprintln!("  {} [input_file] [output_file]", args[0]);
-            eprintln!("  {} [input_file]                (output to stdout)", args[0]);
-            eprintln!("  {}                             (stdin to stdout)", args[0]);
-            std::process::exit(1);
-        }
     };
 
     // Create cleaner with all processors
     let mut cleaner = MarkdownCleaner::new();
-    cleaner.add_processor(Arc::new(WhitespaceProcessor::new()));
-    cleaner.add_processor(Arc::new(ImageProcessor::new()));
-    cleaner.add_processor(Arc::new(TableProcessor::new()));
+    // Checked before any processor rewrites image URLs
+    if options.check_images || options.strict_images {
+        cleaner.add_processor(Arc::new(ImageCheckProcessor::new().with_strict(options.strict_images)));
+    }
+    if options.remove_repeated_images {
+        let mut repeated = RepeatedImageProcessor::new();
+        if let Some(threshold) = options.repeated_image_threshold {
+            repeated = repeated.with_threshold(threshold);
+        }
+        for pattern in &options.allowed_images {
+            repeated = repeated.with_allowed(pattern.clone());
+        }
+        cleaner.add_processor(Arc::new(repeated));
+    }
+    // After decorative images are gone, so a logo is never linked to a caption
+    if options.figure_captions || options.fill_alt {
+        cleaner.add_processor(Arc::new(FigureCaptionProcessor::new().with_fill_alt(options.fill_alt)));
+    }
+    // Columns are reassembled before ruby and list detection look at the lines
+    if options.vertical_text {
+        cleaner.add_processor(Arc::new(VerticalTextProcessor::new()));
+    }
+    // Ruby lines are detected before other processors join or reflow the lines
+    if let Some(output) = options.ruby {
+        cleaner.add_processor(Arc::new(RubyProcessor::new(output)));
+    }
+    if options.html_tables {
+        cleaner.add_processor(Arc::new(HtmlTableProcessor::new()));
+    }
+    if options.aligned_tables {
+        cleaner.add_processor(Arc::new(AlignedTableProcessor::new()));
+    }
+    if options.bullet_lists {
+        cleaner.add_processor(Arc::new(BulletListProcessor::new()));
+    }
+    if options.numbered_lists {
+        cleaner.add_processor(Arc::new(NumberedListProcessor::new()
+            .with_preserve_markers(options.preserve_markers)));
+    }
+    cleaner.add_processor(Arc::new(InvisibleCharProcessor::new()));
+    cleaner.add_processor(Arc::new(LigatureProcessor::new()));
+    if !options.normalize.is_empty() {
+        cleaner.add_processor(Arc::new(NormalizationProcessor::with_policies(&options.normalize)));
+    }
+    // After normalization, which may fold full-width punctuation to ASCII
+    if let Some(style) = options.punctuation {
+        cleaner.add_processor(Arc::new(PunctuationProcessor::new(style)));
+    }
+    cleaner.add_processor(Arc::new(WhitespaceProcessor::with_preset(options.language.unwrap_or(LanguagePreset::Japanese))
+        .with_keep_indent(options.keep_indent)
+        .with_inline_edges(options.cjk_spacing.is_some_and(|policy| policy != SpacingPolicy::Preserve))));
+    if let Some(policy) = options.cjk_spacing {
+        cleaner.add_processor(Arc::new(CjkSpacingProcessor::new(policy)));
+    }
+    cleaner.add_processor(Arc::new(ImageProcessor::new().with_normalization(options.normalize_images)));
+    // Without an output file the result goes to stdout; paths are relative to the working directory
+    let output_dir = options.output.as_deref()
+        .and_then(|path| Path::new(path).parent())
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    if options.rebase_images || options.asset_prefix.is_some() || options.assets_dir.is_some() {
+        let mut assets = ImageAssetProcessor::new();
+        // Copied assets are linked from the output file, wherever the input was
+        if options.rebase_images || options.assets_dir.is_some() {
+            assets = assets.with_output_dir(output_dir);
+        }
+        if let Some(prefix) = &options.asset_prefix {
+            assets = assets.with_asset_prefix(prefix.clone());
+        }
+        if let Some(dir) = &options.assets_dir {
+            assets = assets.with_assets_dir(dir);
+        }
+        cleaner.add_processor(Arc::new(assets));
+    }
+    // After rebasing, which would otherwise resolve the extracted paths against the input
+    if let Some(dir) = &options.extract_data_uris {
+        cleaner.add_processor(Arc::new(DataUriImageProcessor::new(dir).with_output_dir(output_dir)));
+    }
+    cleaner.add_processor(Arc::new(TableProcessor::new().with_repair(options.repair_tables)));
+    // Stitching works on the tables built by the processors above
+    if options.stitch_tables {
+        cleaner.add_processor(Arc::new(TableStitchProcessor::new()));
+    }
+    if options.table_captions {
+        cleaner.add_processor(Arc::new(TableCaptionProcessor::new()));
+    }
+    if let Some(style) = options.linearize_tables {
+        cleaner.add_processor(Arc::new(TableLinearizeProcessor::new(style)));
+    }
 
     // Process the content
-    let cleaned_content = cleaner.clean(&input_content)?;
+    // Processors resolve relative paths (images) against the input file
+    let context = ProcessContext {
+        file_path: options.input.clone(),
+        ..ProcessContext::default()
+    };
+    let (root, report) = cleaner.process_tree(&input_content, context)?;
+    let cleaned_content = cleaner.render(&root);
+
+    // Export tables next to the output (or the input when writing to stdout)
+    if let Some(format) = options.export_tables {
+        let Some(base) = options.output.as_ref().or(options.input.as_ref()) else {
+            bail!("--export-tables needs an input or output file to name the exported files after");
+        };
+        let tables = extract_tables(&root);
+        let written = write_table_exports(Path::new(base), &tables, format)?;
+        eprintln!("Exported {} table(s), manifest written to {}", tables.len(), written.last().map(|path| path.display().to_string()).unwrap_or_default());
+    }
+
+    if let Some(path) = &options.image_manifest {
+        let images = extract_images(&root);
+        write_image_manifest(Path::new(path), &images)?;
+        eprintln!("Listed {} image(s) in {}", images.len(), path);
+    }
 
     // Output the result
-    match args.len() {
-        3 => {
+    match &options.output {
+        Some(path) => {
             // Write to output file
-            fs::write(&args[2], cleaned_content)?;
-            eprintln!("Cleaned content written to {}", args[2]);
+            fs::write(path, cleaned_content)?;
+            eprintln!("Cleaned content written to {}", path);
         }
-        _ => {
+        None => {
             // Output to stdout
             print!("{}", cleaned_content);
         }
     }
 
+    if options.report {
+        eprint!("{}", report);
+    }
+
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,187 @@
+use std::sync::OnceLock;
+use anyhow::Result;
+use markdown::mdast::{AlignKind, Node, Table, TableCell, TableRow, Text};
+use regex::Regex;
+use super::paragraph_lines::{any_line_matches, convert_line_runs, RunItem};
+use super::traits::{NodeProcessor, ProcessContext};
+
+/// Column positions may drift by this many display columns between rows
+const ALIGNMENT_TOLERANCE: usize = 2;
+
+/// A line split into cells at wide gaps, with each cell's display-column span
+#[derive(Debug, Clone)]
+pub(crate) struct AlignedRow {
+    pub cells: Vec<String>,
+    pub spans: Vec<(usize, usize)>,
+}
+
+fn gap_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    // Two or more spaces (ideographic ones included) or a tab; a single ideographic space
+    // separates words in headings such as `第一条　総則`
+    PATTERN.get_or_init(|| Regex::new(r"[ \t\u{3000}]{2,}|\t").expect("valid gap pattern"))
+}
+
+/// Display width of a character in a monospaced layout (East Asian wide = 2)
+pub(crate) fn display_width(ch: char) -> usize {
+    match ch as u32 {
+        0x1100..=0x115F | 0x2E80..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F | 0xFF00..=0xFF60 | 0xFFE0..=0xFFE6 | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+fn text_width(text: &str) -> usize {
+    text.chars().map(display_width).sum()
+}
+
+/// Split a line made only of text into cells at wide whitespace gaps
+pub(crate) fn split_aligned_row(line: &[Node]) -> Option<(AlignedRow, usize)> {
+    let mut text = String::new();
+    for inline in line {
+        match inline {
+            Node::Text(t) => text.push_str(&t.value),
+            _ => return None,
+        }
+    }
+    // Pipe tables are TableProcessor's business
+    if text.contains('|') {
+        return None;
+    }
+
+    let trimmed = text.trim_end();
+    let mut cells = Vec::new();
+    let mut spans = Vec::new();
+    let mut last = 0;
+    let gaps = gap_pattern()
+        .find_iter(trimmed)
+        .map(|gap| (gap.start(), gap.end()))
+        .chain(std::iter::once((trimmed.len(), trimmed.len())));
+
+    for (start, end) in gaps {
+        if start > last {
+            let cell = &trimmed[last..start];
+            let column = text_width(&trimmed[..last]);
+            cells.push(cell.to_string());
+            spans.push((column, column + text_width(cell)));
+        }
+        last = end;
+    }
+
+    if cells.len() < 2 {
+        return None;
+    }
+    Some((AlignedRow { cells, spans }, 0))
+}
+
+fn aligned(a: usize, b: usize) -> bool {
+    a.abs_diff(b) <= ALIGNMENT_TOLERANCE
+}
+
+/// Rows continue a table when they have the same number of cells and every column
+/// after the first starts (left-aligned) or ends (right-aligned) at the same position
+fn rows_align(previous: &AlignedRow, next: &AlignedRow) -> bool {
+    previous.cells.len() == next.cells.len()
+        && previous.spans.iter().zip(&next.spans).skip(1).all(|(p, n)| {
+            aligned(p.0, n.0) || aligned(p.1, n.1)
+        })
+}
+
+/// Whitespace-aligned pseudo-table processor
+/// Many extractors emit tables as space-aligned columns without pipes, which
+/// WhitespaceProcessor would otherwise collapse. Runs of lines (at least `min_rows`)
+/// whose cells line up on wide gaps (two or more spaces, ideographic ones included, or a
+/// tab) become real `Node::Table`s with the first row as header.
+pub struct AlignedTableProcessor {
+    name: String,
+    min_rows: usize,
+}
+
+impl Default for AlignedTableProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AlignedTableProcessor {
+    pub fn new() -> Self {
+        Self {
+            name: "AlignedTableProcessor".to_string(),
+            min_rows: 3,
+        }
+    }
+
+    /// Minimum number of aligned lines required to build a table (default 3)
+    pub fn with_min_rows(mut self, min_rows: usize) -> Self {
+        self.min_rows = min_rows.max(2);
+        self
+    }
+
+    fn make_table(&self, rows: Vec<RunItem<AlignedRow>>, context: &ProcessContext) -> Node {
+        let columns = rows[0].marker.cells.len();
+        context.count(&self.name, "tables", 1);
+        context.count(&self.name, "rows", rows.len());
+
+        // Columns whose cells share their end but not their start are right-aligned
+        let align = (0..columns)
+            .map(|column| {
+                let starts_match = rows.windows(2).all(|w| aligned(w[0].marker.spans[column].0, w[1].marker.spans[column].0));
+                let ends_match = rows.windows(2).all(|w| aligned(w[0].marker.spans[column].1, w[1].marker.spans[column].1));
+                if ends_match && !starts_match { AlignKind::Right } else { AlignKind::None }
+            })
+            .collect();
+
+        let children = rows.into_iter()
+            .map(|row| Node::TableRow(TableRow {
+                children: row.marker.cells.into_iter()
+                    .map(|cell| Node::TableCell(TableCell {
+                        children: vec![Node::Text(Text { value: cell, position: None })],
+                        position: None,
+                    }))
+                    .collect(),
+                position: None,
+            }))
+            .collect();
+
+        Node::Table(Table { children, position: None, align })
+    }
+
+    fn convert_children(&self, children: Vec<Node>, context: &ProcessContext) -> Vec<Node> {
+        convert_line_runs(
+            children,
+            split_aligned_row,
+            rows_align,
+            false,
+            true,
+            self.min_rows,
+            |rows| self.make_table(rows, context),
+        )
+    }
+}
+
+impl NodeProcessor for AlignedTableProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        match node {
+            Node::Root(root) => any_line_matches(&root.children, split_aligned_row),
+            Node::Blockquote(quote) => any_line_matches(&quote.children, split_aligned_row),
+            _ => false,
+        }
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        match &mut node {
+            Node::Root(root) => {
+                root.children = self.convert_children(std::mem::take(&mut root.children), context);
+            }
+            Node::Blockquote(quote) => {
+                quote.children = self.convert_children(std::mem::take(&mut quote.children), context);
+            }
+            _ => {}
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
@@ -0,0 +1,162 @@
+use anyhow::Result;
+use markdown::mdast::{List, ListItem, Node};
+use super::paragraph_lines::{any_line_matches, convert_line_runs, join_lines, line_text, RunItem};
+use super::traits::{NodeProcessor, ProcessContext};
+
+/// Glyphs PDF extractors leave at the start of bulleted lines
+const BULLET_GLYPHS: &[char] = &['•', '●', '・', '▪', '■', '◦', '○', '▫', '□', '◆', '◇', '‣', '⁃'];
+
+/// Pseudo-bullet list processor
+/// PDF bullets come through as paragraphs or lines starting with `•`, `●`, `・` etc.
+/// (or `-` glued to the text); runs of them are converted into real `Node::List`s.
+/// Nesting follows the source indentation when it is known, otherwise the glyph:
+/// a new glyph opens a deeper level, a glyph seen before returns to its level.
+pub struct BulletListProcessor {
+    name: String,
+    min_items: usize,
+}
+
+impl Default for BulletListProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BulletListProcessor {
+    pub fn new() -> Self {
+        Self {
+            name: "BulletListProcessor".to_string(),
+            min_items: 2,
+        }
+    }
+
+    /// Minimum number of consecutive bullets required to build a list (default 2)
+    pub fn with_min_items(mut self, min_items: usize) -> Self {
+        self.min_items = min_items.max(1);
+        self
+    }
+
+    /// Detect a bullet glyph at the start of a line, returning the glyph and its byte length
+    fn bullet_prefix(line: &[Node]) -> Option<(char, usize)> {
+        let text = line_text(line)?;
+
+        let leading = text.len() - text.trim_start().len();
+        let mut chars = text[leading..].chars();
+        let glyph = chars.next()?;
+        let next = chars.next()?;
+
+        if BULLET_GLYPHS.contains(&glyph) {
+            return Some((glyph, leading + glyph.len_utf8()));
+        }
+        // `-` glued to the text; CommonMark already handles `- item`, and `-5` is a number
+        if glyph == '-' && next.is_alphabetic() {
+            return Some((glyph, leading + 1));
+        }
+        None
+    }
+
+    /// Assign nesting levels from indentation where it differs, glyph changes otherwise
+    fn assign_levels(items: &[RunItem<char>]) -> Vec<usize> {
+        let mut stack: Vec<(char, Option<usize>)> = Vec::new();
+        let mut levels = Vec::new();
+
+        for item in items {
+            let level = match (item.indent, stack.last().and_then(|(_, indent)| *indent)) {
+                _ if stack.is_empty() => 0,
+                (Some(indent), Some(top)) if indent > top => stack.len(),
+                (Some(indent), Some(top)) if indent < top => stack.iter()
+                    .position(|(_, level_indent)| level_indent.is_some_and(|i| i >= indent))
+                    .unwrap_or(0),
+                _ => stack.iter()
+                    .rposition(|(glyph, _)| *glyph == item.marker)
+                    .unwrap_or(stack.len()),
+            };
+            stack.truncate(level);
+            stack.push((item.marker, item.indent));
+            levels.push(level);
+        }
+
+        levels
+    }
+
+    fn build_list(items: &mut Vec<(usize, Vec<Vec<Node>>)>, index: &mut usize, level: usize) -> Node {
+        let mut children = Vec::new();
+
+        while *index < items.len() && items[*index].0 >= level {
+            let lines = std::mem::take(&mut items[*index].1);
+            let mut item_children = vec![join_lines(lines)];
+            *index += 1;
+
+            if *index < items.len() && items[*index].0 > level {
+                let child_level = items[*index].0;
+                item_children.push(Self::build_list(items, index, child_level));
+            }
+
+            children.push(Node::ListItem(ListItem {
+                children: item_children,
+                position: None,
+                spread: false,
+                checked: None,
+            }));
+        }
+
+        Node::List(List {
+            children,
+            position: None,
+            ordered: false,
+            start: None,
+            spread: false,
+        })
+    }
+
+    fn make_list(&self, items: Vec<RunItem<char>>, context: &ProcessContext) -> Node {
+        let levels = Self::assign_levels(&items);
+        context.count(&self.name, "items", items.len());
+        context.count(&self.name, "lists", 1);
+
+        let mut leveled: Vec<_> = levels.into_iter()
+            .zip(items.into_iter().map(|item| item.lines))
+            .collect();
+        Self::build_list(&mut leveled, &mut 0, 0)
+    }
+
+    /// Rewrite a container's children, turning bullet runs into lists
+    fn convert_children(&self, children: Vec<Node>, context: &ProcessContext) -> Vec<Node> {
+        convert_line_runs(
+            children,
+            Self::bullet_prefix,
+            |_, _| true,
+            true,
+            true,
+            self.min_items,
+            |items| self.make_list(items, context),
+        )
+    }
+}
+
+impl NodeProcessor for BulletListProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        match node {
+            Node::Root(root) => any_line_matches(&root.children, Self::bullet_prefix),
+            Node::Blockquote(quote) => any_line_matches(&quote.children, Self::bullet_prefix),
+            _ => false,
+        }
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        match &mut node {
+            Node::Root(root) => {
+                root.children = self.convert_children(std::mem::take(&mut root.children), context);
+            }
+            Node::Blockquote(quote) => {
+                quote.children = self.convert_children(std::mem::take(&mut quote.children), context);
+            }
+            _ => {}
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
@@ -0,0 +1,190 @@
+use anyhow::Result;
+use markdown::mdast::{Node, Text};
+use super::text_spans::split_protected;
+use super::traits::{NodeProcessor, ProcessContext};
+use super::whitespace_processor::CharClass;
+
+/// Scripts written without spaces that get a space next to Latin text
+const CJK_CLASSES: [CharClass; 4] = [CharClass::Han, CharClass::Kana, CharClass::Hangul, CharClass::Bopomofo];
+
+/// Spacing between CJK characters and Latin letters or digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpacingPolicy {
+    /// `Rustの実装` → `Rust の実装`
+    Insert,
+    /// `Rust の実装` → `Rustの実装`
+    Remove,
+    /// Leave spacing as extracted
+    Preserve,
+}
+
+fn is_cjk(ch: char) -> bool {
+    CJK_CLASSES.iter().any(|class| class.contains(ch))
+}
+
+fn is_mixed(left: char, right: char) -> bool {
+    (is_cjk(left) && right.is_ascii_alphanumeric()) || (left.is_ascii_alphanumeric() && is_cjk(right))
+}
+
+fn is_blank_text(node: &Node) -> bool {
+    matches!(node, Node::Text(text) if text.value.trim().is_empty())
+}
+
+/// First (or last) visible character of an inline node, looking into emphasis and links
+fn edge_char(node: &Node, last: bool) -> Option<char> {
+    match node {
+        Node::Text(text) if last => text.value.trim_end().chars().last(),
+        Node::Text(text) => text.value.trim_start().chars().next(),
+        Node::InlineCode(code) if last => code.value.chars().last(),
+        Node::InlineCode(code) => code.value.chars().next(),
+        Node::Emphasis(_) | Node::Strong(_) | Node::Link(_) | Node::Delete(_) => {
+            let children = node.children()?;
+            edge_char(if last { children.last()? } else { children.first()? }, last)
+        }
+        _ => None,
+    }
+}
+
+/// Trim the whitespace of a Text node on the side facing a sibling; returns whether any was removed
+fn trim_edge(node: &mut Node, last: bool) -> bool {
+    let Node::Text(text) = node else {
+        return false;
+    };
+    let trimmed = if last { text.value.trim_end() } else { text.value.trim_start() };
+    let changed = trimmed.len() != text.value.len();
+    text.value = trimmed.to_string();
+    changed
+}
+
+/// CJK–Latin spacing processor
+/// Extracted mixed-script text spaces `Rustの実装` and `Rust の実装` inconsistently. Depending on
+/// the policy a space is inserted or removed wherever CJK characters meet ASCII letters or digits,
+/// both inside Text nodes and across inline siblings (`` `cargo`を使う``). The contents of inline
+/// code, URLs, e-mail addresses and math are never touched, nor are their boundaries.
+/// Use it with `WhitespaceProcessor::with_inline_edges(true)` so the spaces between inline
+/// siblings are not trimmed away.
+pub struct CjkSpacingProcessor {
+    name: String,
+    policy: SpacingPolicy,
+}
+
+impl Default for CjkSpacingProcessor {
+    fn default() -> Self {
+        Self::new(SpacingPolicy::Insert)
+    }
+}
+
+impl CjkSpacingProcessor {
+    pub fn new(policy: SpacingPolicy) -> Self {
+        Self {
+            name: "CjkSpacingProcessor".to_string(),
+            policy,
+        }
+    }
+
+    pub fn policy(&self) -> SpacingPolicy {
+        self.policy
+    }
+
+    fn space_text(&self, text: &str, context: &ProcessContext) -> String {
+        // Each character with whether it lies in a protected span
+        let chars: Vec<(char, bool)> = split_protected(text)
+            .into_iter()
+            .flat_map(|span| span.text.chars().map(move |ch| (ch, span.protected)))
+            .collect();
+
+        let mut result = String::with_capacity(text.len());
+        let mut index = 0;
+        while index < chars.len() {
+            let (ch, protected) = chars[index];
+            match self.policy {
+                SpacingPolicy::Insert => {
+                    result.push(ch);
+                    if let Some(&(next, next_protected)) = chars.get(index + 1) {
+                        if !protected && !next_protected && is_mixed(ch, next) {
+                            result.push(' ');
+                            context.count(&self.name, "inserted", 1);
+                        }
+                    }
+                }
+                SpacingPolicy::Remove if matches!(ch, ' ' | '\t') && index > 0 => {
+                    let end = (index..chars.len()).find(|&end| !matches!(chars[end].0, ' ' | '\t')).unwrap_or(chars.len());
+                    let (previous, previous_protected) = chars[index - 1];
+                    let removable = chars.get(end)
+                        .is_some_and(|&(next, next_protected)| !previous_protected && !next_protected && is_mixed(previous, next));
+                    if removable {
+                        context.count(&self.name, "removed", 1);
+                    } else {
+                        result.extend(chars[index..end].iter().map(|(ch, _)| ch));
+                    }
+                    index = end;
+                    continue;
+                }
+                _ => result.push(ch),
+            }
+            index += 1;
+        }
+        result
+    }
+
+    /// Apply the policy where inline siblings (code, images, emphasis, links) meet
+    fn space_siblings(&self, children: &mut Vec<Node>, context: &ProcessContext) {
+        let mut spaced: Vec<Node> = Vec::with_capacity(children.len());
+        for mut child in children.drain(..) {
+            let left = spaced.iter().rposition(|node| !is_blank_text(node));
+            let mixed = left.and_then(|left| edge_char(&spaced[left], true))
+                .zip(edge_char(&child, false))
+                .is_some_and(|(left_char, right_char)| is_mixed(left_char, right_char));
+            if let (Some(left), true) = (left, mixed) {
+                // Whitespace is normalized into a Text node of its own, which survives the
+                // edge handling of `WhitespaceProcessor::with_inline_edges`
+                let mut gap = spaced.len() > left + 1;
+                spaced.truncate(left + 1);
+                gap |= trim_edge(&mut spaced[left], true);
+                gap |= trim_edge(&mut child, false);
+                match self.policy {
+                    SpacingPolicy::Insert => {
+                        spaced.push(Node::Text(Text { value: " ".to_string(), position: None }));
+                        if !gap {
+                            context.count(&self.name, "inserted", 1);
+                        }
+                    }
+                    SpacingPolicy::Remove if gap => context.count(&self.name, "removed", 1),
+                    _ => {}
+                }
+            }
+            spaced.push(child);
+        }
+        *children = spaced;
+    }
+}
+
+impl NodeProcessor for CjkSpacingProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        if self.policy == SpacingPolicy::Preserve {
+            return false;
+        }
+        match node {
+            Node::Text(text) => text.value.chars().any(is_cjk) && text.value.chars().any(|ch| ch.is_ascii_alphanumeric()),
+            Node::Paragraph(_) | Node::Heading(_) | Node::TableCell(_) => node.children().is_some_and(|children| children.len() > 1),
+            _ => false,
+        }
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        match &mut node {
+            Node::Text(text) => text.value = self.space_text(&text.value, context),
+            Node::Paragraph(_) | Node::Heading(_) | Node::TableCell(_) => {
+                if let Some(children) = node.children_mut() {
+                    self.space_siblings(children, context);
+                }
+            }
+            _ => {}
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
@@ -0,0 +1,161 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use markdown::mdast::{Image, Node};
+use super::image_asset_processor::{path_to_url, percent_decode, relative_path};
+use super::traits::{NodeProcessor, ProcessContext};
+
+/// A decoded `data:` URI
+struct DataUri {
+    media_type: String,
+    bytes: Vec<u8>,
+}
+
+fn base64_value(byte: u8) -> Option<u32> {
+    match byte {
+        b'A'..=b'Z' => Some((byte - b'A') as u32),
+        b'a'..=b'z' => Some((byte - b'a') as u32 + 26),
+        b'0'..=b'9' => Some((byte - b'0') as u32 + 52),
+        // Standard and URL-safe alphabets
+        b'+' | b'-' => Some(62),
+        b'/' | b'_' => Some(63),
+        _ => None,
+    }
+}
+
+fn decode_base64(data: &str) -> Option<Vec<u8>> {
+    let mut decoded = Vec::with_capacity(data.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in data.bytes().filter(|byte| !byte.is_ascii_whitespace()) {
+        if byte == b'=' {
+            break;
+        }
+        buffer = (buffer << 6) | base64_value(byte)?;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(decoded)
+}
+
+/// Parse `data:[<media type>][;base64],<data>`
+fn parse_data_uri(url: &str) -> Option<DataUri> {
+    let rest = url.trim().strip_prefix("data:")?;
+    let (header, data) = rest.split_once(',')?;
+    let mut parameters = header.split(';');
+    let media_type = parameters.next().unwrap_or_default().trim().to_lowercase();
+    let base64 = parameters.any(|parameter| parameter.trim().eq_ignore_ascii_case("base64"));
+
+    let bytes = if base64 {
+        decode_base64(&percent_decode(data))?
+    } else {
+        percent_decode(data).into_bytes()
+    };
+    Some(DataUri { media_type, bytes })
+}
+
+fn extension(media_type: &str) -> &'static str {
+    match media_type {
+        "image/png" => "png",
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        "image/bmp" => "bmp",
+        "image/tiff" => "tiff",
+        "image/avif" => "avif",
+        "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+        _ => "bin",
+    }
+}
+
+/// 64-bit FNV-1a; stable across Rust versions, unlike `DefaultHasher`
+pub(crate) fn content_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+/// Data-URI image extraction processor
+/// Some extractors inline images as `data:image/png;base64,...`, which bloats the markdown
+/// and any LLM context it is fed to. Data URIs are decoded into files in an assets directory,
+/// named by content hash so identical images are written once, and the URL is replaced by
+/// the file's path relative to the output location (the input file's directory by default).
+pub struct DataUriImageProcessor {
+    name: String,
+    assets_dir: PathBuf,
+    output_dir: Option<PathBuf>,
+}
+
+impl DataUriImageProcessor {
+    pub fn new(assets_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            name: "DataUriImageProcessor".to_string(),
+            assets_dir: assets_dir.into(),
+            output_dir: None,
+        }
+    }
+
+    /// Make URLs relative to this directory (where the output is written)
+    pub fn with_output_dir(mut self, output_dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = Some(output_dir.into());
+        self
+    }
+
+    fn extract(&self, image: &mut Image, context: &ProcessContext) -> Result<()> {
+        let Some(data) = parse_data_uri(&image.url).filter(|data| data.media_type.starts_with("image/")) else {
+            context.count(&self.name, "invalid", 1);
+            context.record(&self.name, "left data URI in place: not a decodable image", image.position.as_ref());
+            return Ok(());
+        };
+
+        // A file under the hash's name is only a duplicate if its bytes match; hash collisions
+        // and unrelated files get a numbered name instead
+        let stem = format!("image-{:016x}", content_hash(&data.bytes));
+        let mut attempt = 0;
+        let path = loop {
+            let name = if attempt == 0 { stem.clone() } else { format!("{}-{}", stem, attempt) };
+            let path = self.assets_dir.join(format!("{}.{}", name, extension(&data.media_type)));
+            match fs::read(&path) {
+                Ok(existing) if existing == data.bytes => {
+                    context.count(&self.name, "deduplicated", 1);
+                    break path;
+                }
+                Ok(_) => attempt += 1,
+                Err(_) => {
+                    fs::create_dir_all(&self.assets_dir)?;
+                    fs::write(&path, &data.bytes)?;
+                    context.count(&self.name, "extracted", 1);
+                    break path;
+                }
+            }
+        };
+
+        let output_dir = self.output_dir.clone()
+            .or_else(|| context.file_path.as_deref().and_then(|file| Path::new(file).parent()).map(Path::to_path_buf))
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or_else(|| PathBuf::from("."));
+        context.count(&self.name, "data_uri_bytes", image.url.len());
+        image.url = path_to_url(&relative_path(&output_dir, &path));
+        Ok(())
+    }
+}
+
+impl NodeProcessor for DataUriImageProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        matches!(node, Node::Image(image) if image.url.trim_start().starts_with("data:"))
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        if let Node::Image(image) = &mut node {
+            self.extract(image, context)?;
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
@@ -0,0 +1,180 @@
+use std::sync::OnceLock;
+use anyhow::Result;
+use markdown::mdast::Node;
+use regex::Regex;
+use super::paragraph_lines::inline_text;
+use super::traits::{NodeProcessor, ProcessContext};
+
+/// Captions longer than this are body text mentioning a figure, not captions
+const MAX_CAPTION_CHARS: usize = 100;
+
+fn caption_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"^\s*(?:図|Figure|FIGURE|Fig\.)\s*([0-9０-９]+(?:[-‐.．][0-9０-９]+)*)(?:\s*[:：.．]\s*|\s+|$)")
+            .expect("valid caption pattern")
+    })
+}
+
+/// Caption text with whitespace collapsed, if `text` is a figure caption (`図1 構成`, `Figure 2: Setup`)
+fn caption_text(text: &str) -> Option<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (text.chars().count() <= MAX_CAPTION_CHARS && caption_pattern().is_match(&text)).then_some(text)
+}
+
+fn is_blank_text(node: &Node) -> bool {
+    match node {
+        Node::Text(text) => text.value.trim().is_empty(),
+        Node::Break(_) => true,
+        _ => false,
+    }
+}
+
+/// Indices of the images of a paragraph made only of images and whitespace
+fn figure_images(node: &Node) -> Option<Vec<usize>> {
+    let Node::Paragraph(paragraph) = node else {
+        return None;
+    };
+    let images: Vec<usize> = paragraph.children.iter()
+        .enumerate()
+        .filter(|(_, child)| matches!(child, Node::Image(_)))
+        .map(|(index, _)| index)
+        .collect();
+    let only_images = paragraph.children.iter().all(|child| matches!(child, Node::Image(_)) || is_blank_text(child));
+    (!images.is_empty() && only_images).then_some(images)
+}
+
+/// Images followed by a caption in the same paragraph (`![](a.png)\n図1 構成`)
+fn glued_caption(node: &Node) -> Option<(Vec<usize>, String)> {
+    let Node::Paragraph(paragraph) = node else {
+        return None;
+    };
+    let last_image = paragraph.children.iter().rposition(|child| matches!(child, Node::Image(_)))?;
+    let leading = &paragraph.children[..last_image];
+    if !leading.iter().all(|child| matches!(child, Node::Image(_)) || is_blank_text(child)) {
+        return None;
+    }
+    let rest: String = paragraph.children[last_image + 1..].iter().map(inline_text).collect();
+    if !rest.trim_start_matches([' ', '\t']).starts_with('\n') {
+        return None;
+    }
+    let caption = caption_text(&rest)?;
+    let images = (0..=last_image).filter(|&index| matches!(paragraph.children[index], Node::Image(_))).collect();
+    Some((images, caption))
+}
+
+fn paragraph_caption(node: &Node) -> Option<String> {
+    match node {
+        Node::Paragraph(_) => caption_text(&inline_text(node)),
+        _ => None,
+    }
+}
+
+/// Caption paired with each image paragraph of `children`, with the indices of its images:
+/// on the image's next line, in the paragraph below, or in the paragraph above unless an
+/// earlier image already took it
+pub(crate) fn pair_captions(children: &[Node]) -> Vec<Option<(Vec<usize>, String)>> {
+    let mut used = vec![false; children.len()];
+    let mut pairs = Vec::with_capacity(children.len());
+    for index in 0..children.len() {
+        if let Some(glued) = glued_caption(&children[index]) {
+            pairs.push(Some(glued));
+            continue;
+        }
+        let Some(images) = figure_images(&children[index]) else {
+            pairs.push(None);
+            continue;
+        };
+
+        let below = children.get(index + 1).and_then(paragraph_caption).map(|caption| (index + 1, caption));
+        let above = index.checked_sub(1)
+            .filter(|&previous| !used[previous])
+            .and_then(|previous| paragraph_caption(&children[previous]).map(|caption| (previous, caption)));
+        pairs.push(below.or(above).map(|(caption_index, caption)| {
+            used[caption_index] = true;
+            (images, caption)
+        }));
+    }
+    pairs
+}
+
+/// Figure caption detection processor
+/// Figure captions (`図1 システム構成`, `Figure 2: Setup`) sit as loose paragraphs next to
+/// their image, whose alt text is often empty. Captions directly below (preferred) or above
+/// an image paragraph, or on the line after the image in the same paragraph, are linked to
+/// the image in the report. With `fill_alt`, the caption becomes the alt text of images
+/// that have none.
+pub struct FigureCaptionProcessor {
+    name: String,
+    fill_alt: bool,
+}
+
+impl Default for FigureCaptionProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FigureCaptionProcessor {
+    pub fn new() -> Self {
+        Self {
+            name: "FigureCaptionProcessor".to_string(),
+            fill_alt: false,
+        }
+    }
+
+    /// Copy the caption into the alt text of images with an empty alt (default false)
+    pub fn with_fill_alt(mut self, fill_alt: bool) -> Self {
+        self.fill_alt = fill_alt;
+        self
+    }
+
+    fn link(&self, paragraph: &mut Node, images: &[usize], caption: &str, context: &ProcessContext) {
+        let Some(children) = paragraph.children_mut() else {
+            return;
+        };
+        for &index in images {
+            let Node::Image(image) = &mut children[index] else {
+                continue;
+            };
+            context.count(&self.name, "captions", 1);
+            context.record(&self.name, format!("caption \"{}\" linked to image `{}`", caption, image.url), image.position.as_ref());
+            if self.fill_alt && image.alt.trim().is_empty() {
+                image.alt = caption.to_string();
+                context.count(&self.name, "alt_filled", 1);
+            }
+        }
+    }
+
+    fn link_captions(&self, children: &mut [Node], context: &ProcessContext) {
+        for (index, pair) in pair_captions(children).into_iter().enumerate() {
+            if let Some((images, caption)) = pair {
+                self.link(&mut children[index], &images, &caption, context);
+            }
+        }
+    }
+}
+
+impl NodeProcessor for FigureCaptionProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        let children = match node {
+            Node::Root(root) => &root.children,
+            Node::Blockquote(quote) => &quote.children,
+            _ => return false,
+        };
+        children.iter().any(|child| figure_images(child).is_some() || glued_caption(child).is_some())
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        match &mut node {
+            Node::Root(root) => self.link_captions(&mut root.children, context),
+            Node::Blockquote(quote) => self.link_captions(&mut quote.children, context),
+            _ => {}
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
@@ -0,0 +1,380 @@
+use std::sync::OnceLock;
+use anyhow::Result;
+use markdown::mdast::{AlignKind, Image, Node, Table, TableCell, TableRow, Text};
+use regex::Regex;
+use super::traits::{NodeProcessor, ProcessContext};
+
+/// Spans larger than this are treated as 1 (malformed or hostile markup)
+const MAX_SPAN: usize = 100;
+
+/// Tags that only format their content; their text is kept and the tag dropped
+const TRANSPARENT_TAGS: &[&str] = &[
+    "a", "abbr", "b", "code", "div", "em", "font", "i", "mark", "p", "s", "small",
+    "span", "strong", "sub", "sup", "u", "colgroup", "col",
+];
+
+fn tag_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"(?s)<!--.*?-->|<(/?)([a-zA-Z][a-zA-Z0-9]*)((?:[^>'\x22]|'[^']*'|\x22[^\x22]*\x22)*?)(/?)>")
+            .expect("valid tag pattern")
+    })
+}
+
+fn attribute_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r#"([a-zA-Z-]+)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#).expect("valid attribute pattern")
+    })
+}
+
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    attribute_pattern()
+        .captures_iter(attributes)
+        .find(|captures| captures[1].eq_ignore_ascii_case(name))
+        .and_then(|captures| captures.get(2).or(captures.get(3)).or(captures.get(4)))
+        .map(|value| decode_entities(value.as_str()))
+}
+
+fn span(attributes: &str, name: &str) -> usize {
+    attribute(attributes, name)
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|&span| (1..=MAX_SPAN).contains(&span))
+        .unwrap_or(1)
+}
+
+fn alignment(attributes: &str) -> AlignKind {
+    let style = attribute(attributes, "style").unwrap_or_default().to_lowercase().replace(' ', "");
+    let align = attribute(attributes, "align")
+        .map(|align| align.to_lowercase())
+        .or_else(|| style.split(';').find_map(|rule| rule.strip_prefix("text-align:").map(str::to_string)));
+    match align.as_deref() {
+        Some("left") => AlignKind::Left,
+        Some("center") => AlignKind::Center,
+        Some("right") => AlignKind::Right,
+        _ => AlignKind::None,
+    }
+}
+
+/// Decode the character references extractors commonly emit
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest.find(';').filter(|&end| end <= 10).map(|end| &rest[1..end]);
+        let character = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => {
+                let number = entity.strip_prefix('#')?;
+                let code = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => number.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (entity, character) {
+            (Some(entity), Some(character)) => {
+                decoded.push(character);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// A cell as written in the markup, before span expansion
+struct HtmlCell {
+    children: Vec<Node>,
+    header: bool,
+    colspan: usize,
+    rowspan: usize,
+    align: AlignKind,
+}
+
+/// A row as written in the markup
+struct HtmlRow {
+    cells: Vec<HtmlCell>,
+    in_head: bool,
+}
+
+/// Append text to a cell, collapsing whitespace the way HTML rendering does
+fn push_text(children: &mut Vec<Node>, text: &str) {
+    let text = decode_entities(text);
+    let mut collapsed = String::new();
+    for (index, word) in text.split(|ch: char| ch.is_ascii_whitespace()).enumerate() {
+        if index > 0 && !collapsed.ends_with(' ') {
+            collapsed.push(' ');
+        }
+        collapsed.push_str(word);
+    }
+    if collapsed.is_empty() {
+        return;
+    }
+    match children.last_mut() {
+        Some(Node::Text(previous)) => {
+            if previous.value.ends_with(' ') && collapsed.starts_with(' ') {
+                collapsed.remove(0);
+            }
+            previous.value.push_str(&collapsed);
+        }
+        _ => children.push(Node::Text(Text { value: collapsed, position: None })),
+    }
+}
+
+fn trim_cell(children: &mut Vec<Node>) {
+    if let Some(Node::Text(text)) = children.first_mut() {
+        text.value = text.value.trim_start().to_string();
+    }
+    if let Some(Node::Text(text)) = children.last_mut() {
+        text.value = text.value.trim_end().to_string();
+    }
+    children.retain(|node| !matches!(node, Node::Text(text) if text.value.is_empty()));
+}
+
+/// Parse a `<table>` block into rows, or explain why it can't be converted
+fn parse_rows(html: &str) -> std::result::Result<Vec<HtmlRow>, String> {
+    let mut rows: Vec<HtmlRow> = Vec::new();
+    let mut cell: Option<HtmlCell> = None;
+    let mut in_head = false;
+    let mut table_depth = 0;
+    let mut closed = false;
+    let mut last = 0;
+
+    for tag in tag_pattern().captures_iter(html) {
+        let whole = tag.get(0).expect("whole match");
+        let between = &html[last..whole.start()];
+        last = whole.end();
+
+        if closed {
+            return Err("content after </table>".to_string());
+        }
+        match cell.as_mut() {
+            Some(cell) => push_text(&mut cell.children, between),
+            None if !between.trim().is_empty() => return Err("text outside of table cells".to_string()),
+            None => {}
+        }
+        let Some(name) = tag.get(2) else {
+            continue; // comment
+        };
+
+        let name = name.as_str().to_lowercase();
+        let closing = !tag[1].is_empty();
+        let attributes = &tag[3];
+
+        match (name.as_str(), closing) {
+            ("table", false) => {
+                table_depth += 1;
+                if table_depth > 1 {
+                    return Err("nested table".to_string());
+                }
+            }
+            ("table", true) => {
+                if let (Some(open), Some(row)) = (cell.take(), rows.last_mut()) {
+                    row.cells.push(open);
+                }
+                closed = true;
+            }
+            ("thead", _) => in_head = !closing,
+            ("tbody" | "tfoot", _) => in_head = false,
+            ("tr", false) => {
+                // `</td>` and `</tr>` are optional
+                if let (Some(open), Some(row)) = (cell.take(), rows.last_mut()) {
+                    row.cells.push(open);
+                }
+                rows.push(HtmlRow { cells: Vec::new(), in_head });
+            }
+            ("td" | "th", false) => {
+                if rows.is_empty() {
+                    rows.push(HtmlRow { cells: Vec::new(), in_head });
+                }
+                if let Some(open) = cell.take() {
+                    rows.last_mut().expect("row exists").cells.push(open);
+                }
+                cell = Some(HtmlCell {
+                    children: Vec::new(),
+                    header: name == "th",
+                    colspan: span(attributes, "colspan"),
+                    rowspan: span(attributes, "rowspan"),
+                    align: alignment(attributes),
+                });
+            }
+            ("td" | "th" | "tr", true) => {
+                if let (Some(open), Some(row)) = (cell.take(), rows.last_mut()) {
+                    row.cells.push(open);
+                }
+            }
+            ("br", _) => {
+                if let Some(cell) = cell.as_mut() {
+                    push_text(&mut cell.children, " ");
+                }
+            }
+            ("img", false) => {
+                let Some(cell) = cell.as_mut() else {
+                    return Err("image outside of table cells".to_string());
+                };
+                cell.children.push(Node::Image(Image {
+                    alt: attribute(attributes, "alt").unwrap_or_default(),
+                    url: attribute(attributes, "src").unwrap_or_default(),
+                    title: attribute(attributes, "title"),
+                    position: None,
+                }));
+            }
+            (name, _) if TRANSPARENT_TAGS.contains(&name) => {}
+            (name, _) => return Err(format!("unsupported element <{}>", name)),
+        }
+    }
+
+    if !closed {
+        return Err("missing </table> (blank line inside the table?)".to_string());
+    }
+    if !html[last..].trim().is_empty() {
+        return Err("content after </table>".to_string());
+    }
+    rows.retain(|row| !row.cells.is_empty());
+    if rows.is_empty() {
+        return Err("no rows".to_string());
+    }
+    Ok(rows)
+}
+
+/// Lay cells out on a grid, repeating spanned cells in every slot they cover;
+/// returns the grid, its column count and the number of slots filled by repetition
+fn expand_spans(rows: Vec<HtmlRow>) -> (Vec<Vec<Vec<Node>>>, usize, usize) {
+    let mut grid: Vec<Vec<Option<Vec<Node>>>> = Vec::new();
+    let mut expanded = 0;
+
+    for (row_index, row) in rows.into_iter().enumerate() {
+        if grid.len() <= row_index {
+            grid.resize(row_index + 1, Vec::new());
+        }
+        let mut column = 0;
+        for mut cell in row.cells {
+            trim_cell(&mut cell.children);
+            while grid[row_index].get(column).is_some_and(|slot| slot.is_some()) {
+                column += 1;
+            }
+            for r in row_index..row_index + cell.rowspan {
+                if grid.len() <= r {
+                    grid.resize(r + 1, Vec::new());
+                }
+                for c in column..column + cell.colspan {
+                    if grid[r].len() <= c {
+                        grid[r].resize(c + 1, None);
+                    }
+                    grid[r][c] = Some(cell.children.clone());
+                }
+            }
+            expanded += cell.colspan * cell.rowspan - 1;
+            column += cell.colspan;
+        }
+    }
+
+    let columns = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+    let grid = grid.into_iter()
+        .map(|row| row.into_iter().map(|slot| slot.unwrap_or_default()).collect())
+        .collect();
+    (grid, columns, expanded)
+}
+
+/// HTML table conversion processor
+/// Some extractors emit complex tables as `<table>` HTML blocks, which nothing else
+/// cleans and which cost many tokens. Simple tables (thead/tbody/tr/th/td, formatting
+/// tags, `<br>`, `<img>`) become `Node::Table`s; `colspan`/`rowspan` are expanded by
+/// repeating the cell in every slot it covers. Tables that can't be converted (nested
+/// tables, lists inside cells, ragged rows) are left intact with the reason reported.
+pub struct HtmlTableProcessor {
+    name: String,
+}
+
+impl Default for HtmlTableProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HtmlTableProcessor {
+    pub fn new() -> Self {
+        Self {
+            name: "HtmlTableProcessor".to_string(),
+        }
+    }
+
+    fn convert(&self, html: &str, context: &ProcessContext) -> std::result::Result<Table, String> {
+        let rows = parse_rows(html)?;
+        let align: Vec<AlignKind> = rows[0].cells.iter()
+            .flat_map(|cell| std::iter::repeat_n(cell.align, cell.colspan))
+            .collect();
+
+        // Rows of <thead> (or a leading all-<th> row) form the header; GFM has one header row
+        let header_rows = rows.iter()
+            .take_while(|row| row.in_head || row.cells.iter().all(|cell| cell.header))
+            .count();
+        if header_rows > 1 {
+            context.record(&self.name, format!("{} header rows; rows after the first become body rows", header_rows), None);
+        }
+
+        let (grid, columns, expanded) = expand_spans(rows);
+        if grid.iter().any(|row| row.len() != columns) {
+            return Err("rows have different column counts after expanding spans".to_string());
+        }
+        context.count(&self.name, "spans_expanded", expanded);
+
+        let mut align = align;
+        align.resize(columns, AlignKind::None);
+        let children = grid.into_iter()
+            .map(|row| Node::TableRow(TableRow {
+                children: row.into_iter()
+                    .map(|cell| Node::TableCell(TableCell { children: cell, position: None }))
+                    .collect(),
+                position: None,
+            }))
+            .collect();
+
+        Ok(Table { children, position: None, align })
+    }
+}
+
+impl NodeProcessor for HtmlTableProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        match node {
+            Node::Html(html) => html.value.trim_start().to_lowercase().starts_with("<table"),
+            _ => false,
+        }
+    }
+
+    fn process_node(&self, node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        let Node::Html(html) = &node else {
+            return Ok(Some(node));
+        };
+
+        match self.convert(html.value.trim(), context) {
+            Ok(mut table) => {
+                context.count(&self.name, "tables_converted", 1);
+                table.position = html.position.clone();
+                Ok(Some(Node::Table(table)))
+            }
+            Err(reason) => {
+                context.count(&self.name, "tables_skipped", 1);
+                context.record(&self.name, format!("left HTML table intact: {}", reason), html.position.as_ref());
+                Ok(Some(node))
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
@@ -0,0 +1,217 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use anyhow::Result;
+use markdown::mdast::{Image, Node};
+use super::traits::{NodeProcessor, ProcessContext};
+
+/// Whether an image URL points at a local file relative to the document
+pub(crate) fn is_relative_local(url: &str) -> bool {
+    let url = url.trim();
+    !url.is_empty()
+        && !url.contains("://")
+        && !url.starts_with("data:")
+        && !url.starts_with("mailto:")
+        && !url.starts_with('/')
+        && !url.starts_with('#')
+        && !Path::new(url).is_absolute()
+}
+
+/// Decode `%XX` escapes so URLs can be used as file paths
+pub(crate) fn percent_decode(url: &str) -> String {
+    let bytes = url.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes.get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Path components as a URL path, with `%` and spaces encoded
+pub(crate) fn path_to_url(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy().replace('%', "%25").replace(' ', "%20"))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Absolute path with `.` and `..` resolved lexically
+fn normalize_path(path: &Path) -> PathBuf {
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Path of `target` relative to the directory `from`
+pub(crate) fn relative_path(from: &Path, target: &Path) -> PathBuf {
+    let from = normalize_path(from);
+    let target = normalize_path(target);
+    let from: Vec<Component> = from.components().collect();
+    let target: Vec<Component> = target.components().collect();
+    let common = from.iter().zip(&target).take_while(|(a, b)| a == b).count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..from.len() {
+        relative.push("..");
+    }
+    for component in &target[common..] {
+        relative.push(component);
+    }
+    relative
+}
+
+/// Image path rebasing and asset collection processor
+/// Relative image URLs only work next to the document they were extracted with. URLs are
+/// resolved against the input file (`ProcessContext::file_path`), referenced local images can
+/// be copied into an assets directory, and URLs are rewritten relative to the output location
+/// or under an asset prefix (e.g. a CDN base URL). Remote URLs and data URIs are left alone.
+pub struct ImageAssetProcessor {
+    name: String,
+    output_dir: Option<PathBuf>,
+    asset_prefix: Option<String>,
+    assets_dir: Option<PathBuf>,
+}
+
+impl Default for ImageAssetProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImageAssetProcessor {
+    pub fn new() -> Self {
+        Self {
+            name: "ImageAssetProcessor".to_string(),
+            output_dir: None,
+            asset_prefix: None,
+            assets_dir: None,
+        }
+    }
+
+    /// Rewrite URLs relative to this directory (where the output is written)
+    pub fn with_output_dir(mut self, output_dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = Some(output_dir.into());
+        self
+    }
+
+    /// Rewrite URLs as `<prefix>/<path>` instead of relative paths
+    pub fn with_asset_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.asset_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Copy referenced local images into this directory
+    pub fn with_assets_dir(mut self, assets_dir: impl Into<PathBuf>) -> Self {
+        self.assets_dir = Some(assets_dir.into());
+        self
+    }
+
+    fn input_dir(context: &ProcessContext) -> PathBuf {
+        context.file_path.as_deref()
+            .and_then(|path| Path::new(path).parent())
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    /// Copy `source` into the assets directory, renaming on name clashes with different
+    /// content; returns the copy's path
+    fn copy_asset(&self, assets_dir: &Path, source: &Path) -> Result<PathBuf> {
+        fs::create_dir_all(assets_dir)?;
+        let content = fs::read(source)?;
+        let stem = source.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        let extension = source.extension().map(|ext| format!(".{}", ext.to_string_lossy())).unwrap_or_default();
+
+        let mut attempt = 0;
+        loop {
+            let name = if attempt == 0 { format!("{}{}", stem, extension) } else { format!("{}-{}{}", stem, attempt, extension) };
+            let destination = assets_dir.join(name);
+            match fs::read(&destination) {
+                Ok(existing) if existing == content => return Ok(destination),
+                Ok(_) => attempt += 1,
+                Err(_) => {
+                    fs::write(&destination, &content)?;
+                    return Ok(destination);
+                }
+            }
+        }
+    }
+
+    fn rebase(&self, image: &mut Image, context: &ProcessContext) -> Result<()> {
+        let input_dir = Self::input_dir(context);
+        let original = percent_decode(image.url.trim());
+        let source = input_dir.join(&original);
+
+        let target = match &self.assets_dir {
+            Some(assets_dir) if source.is_file() => {
+                let copy = self.copy_asset(assets_dir, &source)?;
+                context.count(&self.name, "copied", 1);
+                copy
+            }
+            Some(_) => {
+                context.count(&self.name, "missing", 1);
+                context.record(&self.name, format!("image not found, not copied: {}", source.display()), image.position.as_ref());
+                return Ok(());
+            }
+            None => source,
+        };
+
+        let url = match (&self.asset_prefix, &self.assets_dir) {
+            (Some(prefix), Some(assets_dir)) => {
+                format!("{}/{}", prefix.trim_end_matches('/'), path_to_url(&relative_path(assets_dir, &target)))
+            }
+            (Some(prefix), None) => {
+                format!("{}/{}", prefix.trim_end_matches('/'), path_to_url(&relative_path(&input_dir, &target)))
+            }
+            (None, _) => {
+                let output_dir = self.output_dir.clone().unwrap_or_else(|| input_dir.clone());
+                path_to_url(&relative_path(&output_dir, &target))
+            }
+        };
+
+        if url != image.url {
+            context.count(&self.name, "rebased", 1);
+            image.url = url;
+        }
+        Ok(())
+    }
+}
+
+impl NodeProcessor for ImageAssetProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        let configured = self.output_dir.is_some() || self.asset_prefix.is_some() || self.assets_dir.is_some();
+        matches!(node, Node::Image(image) if configured && is_relative_local(&image.url))
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        if let Node::Image(image) = &mut node {
+            self.rebase(image, context)?;
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
@@ -0,0 +1,118 @@
+use std::path::{Path, PathBuf};
+use anyhow::{bail, Result};
+use markdown::mdast::{Image, Node};
+use super::image_asset_processor::{is_relative_local, percent_decode};
+use super::traits::{NodeProcessor, ProcessContext};
+
+fn collect_images<'a>(node: &'a Node, images: &mut Vec<&'a Image>) {
+    match node {
+        Node::Image(image) => images.push(image),
+        other => {
+            if let Some(children) = other.children() {
+                for child in children {
+                    collect_images(child, images);
+                }
+            }
+        }
+    }
+}
+
+/// `C:\x.png`, `C:/x.png`
+fn is_drive_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && matches!(bytes[2], b'\\' | b'/')
+}
+
+/// Where a local image URL points on disk: relative URLs resolve against the input
+/// directory, absolute paths and `file://` URLs are taken as they are
+fn local_path(url: &str, input_dir: &Path) -> Option<PathBuf> {
+    let url = url.trim();
+    if let Some(rest) = url.strip_prefix("file://") {
+        let path = percent_decode(rest.strip_prefix("localhost").unwrap_or(rest));
+        // `file:///C:/x.png`
+        let path = path.strip_prefix('/').filter(|path| is_drive_path(path)).map(str::to_string).unwrap_or(path);
+        return Some(PathBuf::from(path));
+    }
+    if url.starts_with('/') || is_drive_path(url) || Path::new(url).is_absolute() {
+        return Some(PathBuf::from(percent_decode(url)));
+    }
+    is_relative_local(url).then(|| input_dir.join(percent_decode(url)))
+}
+
+/// Missing-image verification processor
+/// Extractor runs sometimes write the markdown but not the images it references. Every
+/// local image URL is resolved against the input file (`ProcessContext::file_path`), absolute
+/// paths and `file://` URLs are checked as they are, and missing files are reported with their
+/// line and column. Windows drive paths cannot be checked elsewhere and are reported unchecked. In strict mode a missing image
+/// fails the run, listing every missing file.
+pub struct ImageCheckProcessor {
+    name: String,
+    strict: bool,
+}
+
+impl Default for ImageCheckProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImageCheckProcessor {
+    pub fn new() -> Self {
+        Self {
+            name: "ImageCheckProcessor".to_string(),
+            strict: false,
+        }
+    }
+
+    /// Fail processing when any referenced local image is missing (default false)
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+}
+
+impl NodeProcessor for ImageCheckProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        matches!(node, Node::Root(_))
+    }
+
+    fn process_node(&self, node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        let input_dir = context.file_path.as_deref()
+            .and_then(|path| Path::new(path).parent())
+            .unwrap_or(Path::new(""));
+
+        let mut images = Vec::new();
+        collect_images(&node, &mut images);
+
+        let mut missing = Vec::new();
+        for image in images {
+            let Some(path) = local_path(&image.url, input_dir) else {
+                continue;
+            };
+            if !cfg!(windows) && is_drive_path(&path.to_string_lossy()) {
+                context.count(&self.name, "unchecked", 1);
+                context.record(&self.name, format!("unchecked image (Windows path): {}", image.url), image.position.as_ref());
+                continue;
+            }
+            context.count(&self.name, "checked", 1);
+            if path.is_file() {
+                continue;
+            }
+            context.count(&self.name, "missing", 1);
+            context.record(&self.name, format!("missing image: {}", image.url), image.position.as_ref());
+            missing.push(match &image.position {
+                Some(position) => format!("{} ({}:{})", image.url, position.start.line, position.start.column),
+                None => image.url.clone(),
+            });
+        }
+
+        if self.strict && !missing.is_empty() {
+            bail!("{} missing image(s): {}", missing.len(), missing.join(", "));
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
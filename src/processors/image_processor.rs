@@ -1,29 +1,156 @@
+use std::sync::OnceLock;
 use anyhow::Result;
-use markdown::mdast::Node;
+use markdown::mdast::{Image, Node, Text};
+use regex::Regex;
 use super::traits::{NodeProcessor, ProcessContext};
 
+fn broken_image_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    // `![alt](my image.png)` that CommonMark leaves as text because of the spaces
+    PATTERN.get_or_init(|| {
+        Regex::new(r#"!\[([^\]\n]*)\]\(([^)"\n]*?)(?:\s+"([^"\n]*)")?\s*\)"#).expect("valid image pattern")
+    })
+}
+
+/// Collapse whitespace runs (ideographic spaces included) to one space and trim
+fn normalize_alt(alt: &str) -> String {
+    alt.split(|ch: char| ch.is_whitespace()).filter(|word| !word.is_empty()).collect::<Vec<_>>().join(" ")
+}
+
+/// Fix the URL damage extractors leave: padding, spaces around `/`, Windows
+/// backslashes, double-encoded or stray `%`, and unencoded spaces
+fn normalize_url(url: &str) -> String {
+    let url = url.trim();
+    if url.starts_with("data:") {
+        return url.chars().filter(|ch| !ch.is_whitespace()).collect();
+    }
+
+    let url = url.replace('\\', "/");
+    let segments: Vec<&str> = url.split('/').map(str::trim).collect();
+    let url = segments.join("/");
+
+    let chars: Vec<char> = url.chars().collect();
+    let is_hex = |index: usize| chars.get(index).is_some_and(char::is_ascii_hexdigit);
+    let mut normalized = String::with_capacity(url.len());
+    let mut index = 0;
+    while index < chars.len() {
+        match chars[index] {
+            // `%2520` is `%20` encoded twice
+            '%' if chars[index + 1..].starts_with(&['2', '5']) && is_hex(index + 3) && is_hex(index + 4) => {
+                normalized.push('%');
+                index += 2;
+            }
+            '%' if !(is_hex(index + 1) && is_hex(index + 2)) => normalized.push_str("%25"),
+            ch if ch.is_whitespace() => normalized.push_str("%20"),
+            ch => normalized.push(ch),
+        }
+        index += 1;
+    }
+    normalized
+}
+
 /// Image preservation processor
 /// Ensures that image references ![alt](url) are preserved exactly as-is
 /// This is critical for AI-extracted content where images are important
+/// With normalization enabled, alt text whitespace is trimmed and collapsed, URLs are
+/// repaired (padding, backslashes, spaces, broken percent-encoding) and image syntax left
+/// as text because of spaces in the URL is recovered; titles are kept.
 pub struct ImageProcessor {
     name: String,
+    normalize: bool,
+}
+
+impl Default for ImageProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ImageProcessor {
     pub fn new() -> Self {
         Self {
             name: "ImageProcessor".to_string(),
+            normalize: false,
+        }
+    }
+
+    /// Normalize alt text and URLs instead of preserving them exactly (default false)
+    pub fn with_normalization(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    fn normalize_image(&self, image: &mut Image, context: &ProcessContext) {
+        let alt = normalize_alt(&image.alt);
+        if alt != image.alt {
+            context.count(&self.name, "alt_normalized", 1);
+            image.alt = alt;
+        }
+        let url = normalize_url(&image.url);
+        if url != image.url {
+            context.count(&self.name, "url_normalized", 1);
+            context.record(&self.name, format!("image URL `{}` normalized to `{}`", image.url, url), image.position.as_ref());
+            image.url = url;
         }
     }
+
+    /// Turn `![alt](path with spaces.png)` left in text nodes into image nodes
+    fn recover_images(&self, children: Vec<Node>, context: &ProcessContext) -> Vec<Node> {
+        let mut result = Vec::new();
+        for child in children {
+            let Node::Text(text) = &child else {
+                result.push(child);
+                continue;
+            };
+            let mut last = 0;
+            for captures in broken_image_pattern().captures_iter(&text.value) {
+                let whole = captures.get(0).expect("whole match");
+                if whole.start() > last {
+                    result.push(Node::Text(Text { value: text.value[last..whole.start()].to_string(), position: None }));
+                }
+                result.push(Node::Image(Image {
+                    alt: captures[1].to_string(),
+                    url: captures[2].to_string(),
+                    title: captures.get(3).map(|title| title.as_str().to_string()),
+                    position: None,
+                }));
+                context.count(&self.name, "recovered", 1);
+                context.record(&self.name, format!("recovered image syntax `{}`", whole.as_str()), text.position.as_ref());
+                last = whole.end();
+            }
+            if last == 0 {
+                result.push(child);
+            } else if last < text.value.len() {
+                result.push(Node::Text(Text { value: text.value[last..].to_string(), position: None }));
+            }
+        }
+        result
+    }
 }
 
 impl NodeProcessor for ImageProcessor {
     fn should_process(&self, node: &Node) -> bool {
-        matches!(node, Node::Image(_))
+        match node {
+            Node::Image(_) => true,
+            Node::Paragraph(paragraph) if self.normalize => paragraph.children.iter().any(|child| {
+                matches!(child, Node::Text(text) if broken_image_pattern().is_match(&text.value))
+            }),
+            _ => false,
+        }
     }
 
-    fn process_node(&self, node: Node, _context: &ProcessContext) -> Result<Option<Node>> {
-        // Images are preserved as-is - no modification needed
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        // Without normalization images are preserved as-is
+        if !self.normalize {
+            return Ok(Some(node));
+        }
+        match &mut node {
+            Node::Image(image) => self.normalize_image(image, context),
+            Node::Paragraph(paragraph) => {
+                paragraph.children = self.recover_images(std::mem::take(&mut paragraph.children), context);
+            }
+            _ => {}
+        }
         Ok(Some(node))
     }
 
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use markdown::mdast::Node;
+use markdown::unist::{Point, Position};
+use super::traits::{NodeProcessor, ProcessContext};
+
+/// Categories of invisible or garbage characters found in PDF-extracted text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InvisibleCategory {
+    /// Zero-width space and invisible operators (U+200B, U+2060..U+2064)
+    ZeroWidth,
+    /// Joiners and bidi marks that carry meaning: ZWNJ/ZWJ (emoji sequences, Persian and Indic
+    /// shaping), LRM/RLM, embeddings and isolates (U+200C..U+200F, U+202A..U+202E, U+2066..U+2069)
+    JoinerOrBidi,
+    /// Soft hyphen (U+00AD)
+    SoftHyphen,
+    /// Byte order mark (U+FEFF) in the middle of the text
+    ByteOrderMark,
+    /// Replacement character (U+FFFD) left by failed glyph decoding
+    ReplacementCharacter,
+    /// Private use area glyphs (U+E000..U+F8FF and the supplementary PUA planes)
+    PrivateUse,
+    /// C0/C1 control characters other than tab and line breaks
+    Control,
+}
+
+impl InvisibleCategory {
+    pub fn of(ch: char) -> Option<Self> {
+        match ch as u32 {
+            0x200B | 0x2060..=0x2064 => Some(Self::ZeroWidth),
+            0x200C..=0x200F | 0x202A..=0x202E | 0x2066..=0x2069 => Some(Self::JoinerOrBidi),
+            0x00AD => Some(Self::SoftHyphen),
+            0xFEFF => Some(Self::ByteOrderMark),
+            0xFFFD => Some(Self::ReplacementCharacter),
+            0xE000..=0xF8FF | 0xF0000..=0xFFFFD | 0x100000..=0x10FFFD => Some(Self::PrivateUse),
+            0x09 | 0x0A | 0x0D => None,
+            0x00..=0x1F | 0x7F..=0x9F => Some(Self::Control),
+            _ => None,
+        }
+    }
+
+    /// Counter / report label for this category
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ZeroWidth => "zero_width",
+            Self::JoinerOrBidi => "joiner_bidi",
+            Self::SoftHyphen => "soft_hyphen",
+            Self::ByteOrderMark => "byte_order_mark",
+            Self::ReplacementCharacter => "replacement_character",
+            Self::PrivateUse => "private_use",
+            Self::Control => "control",
+        }
+    }
+}
+
+/// What to do with characters of a category
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvisibleAction {
+    /// Leave the characters alone
+    Keep,
+    /// Delete the characters, counting them in the report
+    Remove,
+    /// Leave the characters in place and report each position
+    Flag,
+}
+
+/// Invisible and control character scrubbing processor
+/// Removes zero-width spaces, soft hyphens, stray BOMs and control characters,
+/// and flags U+FFFD / private-use glyphs with their positions so garbled pages can be found.
+/// Joiners and bidi marks are kept by default since emoji, shaping and RTL text depend on them.
+/// Each category's action is configurable; only Text nodes are touched.
+pub struct InvisibleCharProcessor {
+    name: String,
+    actions: HashMap<InvisibleCategory, InvisibleAction>,
+}
+
+impl Default for InvisibleCharProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InvisibleCharProcessor {
+    pub fn new() -> Self {
+        let actions = HashMap::from([
+            (InvisibleCategory::ZeroWidth, InvisibleAction::Remove),
+            (InvisibleCategory::JoinerOrBidi, InvisibleAction::Keep),
+            (InvisibleCategory::SoftHyphen, InvisibleAction::Remove),
+            (InvisibleCategory::ByteOrderMark, InvisibleAction::Remove),
+            (InvisibleCategory::ReplacementCharacter, InvisibleAction::Flag),
+            (InvisibleCategory::PrivateUse, InvisibleAction::Flag),
+            (InvisibleCategory::Control, InvisibleAction::Remove),
+        ]);
+
+        Self {
+            name: "InvisibleCharProcessor".to_string(),
+            actions,
+        }
+    }
+
+    /// Override the action taken for a category
+    pub fn with_action(mut self, category: InvisibleCategory, action: InvisibleAction) -> Self {
+        self.actions.insert(category, action);
+        self
+    }
+
+    pub fn action(&self, category: InvisibleCategory) -> InvisibleAction {
+        self.actions.get(&category).copied().unwrap_or(InvisibleAction::Keep)
+    }
+
+    fn scrub(&self, text: &str, position: Option<&Position>, context: &ProcessContext) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut points: Option<Vec<Option<Point>>> = None;
+
+        for (index, ch) in text.chars().enumerate() {
+            let action = InvisibleCategory::of(ch).map(|category| (category, self.action(category)));
+
+            match action {
+                Some((category, InvisibleAction::Remove)) => {
+                    context.count(&self.name, &format!("removed.{}", category.label()), 1);
+                }
+                Some((category, InvisibleAction::Flag)) => {
+                    context.count(&self.name, &format!("flagged.{}", category.label()), 1);
+                    let points = points.get_or_insert_with(|| source_points(text, position, context.source.as_deref()));
+                    let position = points[index].as_ref().map(|p| Position {
+                        start: p.clone(),
+                        end: Point::new(p.line, p.column + ch.len_utf8(), p.offset + ch.len_utf8()),
+                    });
+                    context.record(
+                        &self.name,
+                        format!("{} U+{:04X}", category.label(), ch as u32),
+                        position.as_ref(),
+                    );
+                    result.push(ch);
+                }
+                _ => result.push(ch),
+            }
+        }
+
+        result
+    }
+}
+
+/// Source point of each character of a Text value, with byte columns as markdown-rs counts
+/// them. With the source, characters are looked up in the node's span so container prefixes
+/// (`> `, list indentation) and escapes left out of the value are skipped; without it, points
+/// are counted from the node's start.
+fn source_points(text: &str, position: Option<&Position>, source: Option<&str>) -> Vec<Option<Point>> {
+    let Some(position) = position else {
+        return vec![None; text.chars().count()];
+    };
+    let Some(span) = source.and_then(|source| source.get(..position.end.offset)) else {
+        let mut point = position.start.clone();
+        return text.chars()
+            .map(|ch| {
+                let current = point.clone();
+                point.offset += ch.len_utf8();
+                if ch == '\n' {
+                    point.line += 1;
+                    point.column = 1;
+                } else {
+                    point.column += ch.len_utf8();
+                }
+                Some(current)
+            })
+            .collect();
+    };
+
+    let mut cursor = position.start.offset;
+    text.chars()
+        .map(|ch| {
+            let offset = cursor + span.get(cursor..)?.find(ch)?;
+            cursor = offset + ch.len_utf8();
+            let line_start = span[..offset].rfind('\n').map_or(0, |newline| newline + 1);
+            let line = position.start.line + span[position.start.offset..offset].matches('\n').count();
+            Some(Point::new(line, offset - line_start + 1, offset))
+        })
+        .collect()
+}
+
+impl NodeProcessor for InvisibleCharProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        match node {
+            Node::Text(text) => text.value.chars().any(|ch| {
+                InvisibleCategory::of(ch).is_some_and(|category| self.action(category) != InvisibleAction::Keep)
+            }),
+            _ => false,
+        }
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        if let Node::Text(text) = &mut node {
+            text.value = self.scrub(&text.value, text.position.as_ref(), context);
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use markdown::mdast::Node;
+use super::traits::{NodeProcessor, ProcessContext};
+
+/// Latin ligatures from the Alphabetic Presentation Forms block (U+FB00)
+const LATIN_LIGATURES: &[(char, &str)] = &[
+    ('\u{FB00}', "ff"),
+    ('\u{FB01}', "fi"),
+    ('\u{FB02}', "fl"),
+    ('\u{FB03}', "ffi"),
+    ('\u{FB04}', "ffl"),
+    ('\u{FB05}', "st"),
+    ('\u{FB06}', "st"),
+    ('\u{0132}', "IJ"),
+    ('\u{0133}', "ij"),
+];
+
+/// CJK vertical presentation forms (U+FE10, U+FE30 blocks) emitted for tategaki text
+const CJK_PRESENTATION_FORMS: &[(char, &str)] = &[
+    ('\u{FE10}', "，"),
+    ('\u{FE11}', "、"),
+    ('\u{FE12}', "。"),
+    ('\u{FE13}', "："),
+    ('\u{FE14}', "；"),
+    ('\u{FE15}', "！"),
+    ('\u{FE16}', "？"),
+    ('\u{FE17}', "〖"),
+    ('\u{FE18}', "〗"),
+    ('\u{FE19}', "…"),
+    ('\u{FE30}', "‥"),
+    ('\u{FE31}', "—"),
+    ('\u{FE32}', "–"),
+    ('\u{FE33}', "_"),
+    ('\u{FE34}', "_"),
+    ('\u{FE35}', "（"),
+    ('\u{FE36}', "）"),
+    ('\u{FE37}', "｛"),
+    ('\u{FE38}', "｝"),
+    ('\u{FE39}', "〔"),
+    ('\u{FE3A}', "〕"),
+    ('\u{FE3B}', "【"),
+    ('\u{FE3C}', "】"),
+    ('\u{FE3D}', "《"),
+    ('\u{FE3E}', "》"),
+    ('\u{FE3F}', "〈"),
+    ('\u{FE40}', "〉"),
+    ('\u{FE41}', "「"),
+    ('\u{FE42}', "」"),
+    ('\u{FE43}', "『"),
+    ('\u{FE44}', "』"),
+    ('\u{FE47}', "［"),
+    ('\u{FE48}', "］"),
+];
+
+/// Arabic Presentation Forms-B (U+FE80..U+FEF4): each base letter followed by
+/// the number of contextual forms (isolated, final, initial, medial) it occupies
+const ARABIC_CONTEXTUAL_FORMS: &[(char, u32)] = &[
+    ('\u{0621}', 1), ('\u{0622}', 2), ('\u{0623}', 2), ('\u{0624}', 2),
+    ('\u{0625}', 2), ('\u{0626}', 4), ('\u{0627}', 2), ('\u{0628}', 4),
+    ('\u{0629}', 2), ('\u{062A}', 4), ('\u{062B}', 4), ('\u{062C}', 4),
+    ('\u{062D}', 4), ('\u{062E}', 4), ('\u{062F}', 2), ('\u{0630}', 2),
+    ('\u{0631}', 2), ('\u{0632}', 2), ('\u{0633}', 4), ('\u{0634}', 4),
+    ('\u{0635}', 4), ('\u{0636}', 4), ('\u{0637}', 4), ('\u{0638}', 4),
+    ('\u{0639}', 4), ('\u{063A}', 4), ('\u{0641}', 4), ('\u{0642}', 4),
+    ('\u{0643}', 4), ('\u{0644}', 4), ('\u{0645}', 4), ('\u{0646}', 4),
+    ('\u{0647}', 4), ('\u{0648}', 2), ('\u{0649}', 2), ('\u{064A}', 4),
+];
+
+/// Arabic lam-alef ligatures (U+FEF5..U+FEFC), isolated and final forms
+const ARABIC_LAM_ALEF: &[(u32, &str)] = &[
+    (0xFEF5, "\u{0644}\u{0622}"),
+    (0xFEF7, "\u{0644}\u{0623}"),
+    (0xFEF9, "\u{0644}\u{0625}"),
+    (0xFEFB, "\u{0644}\u{0627}"),
+];
+
+/// Ligature and presentation-form expansion processor
+/// PDF text layers keep typographic ligatures (ﬁ, ﬂ, ...) and presentation forms
+/// that break search and embeddings; this expands them back to plain characters.
+/// Only Text nodes are touched, so inline code and code blocks keep their exact content.
+pub struct LigatureProcessor {
+    name: String,
+    table: HashMap<char, String>,
+}
+
+impl Default for LigatureProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LigatureProcessor {
+    pub fn new() -> Self {
+        let mut table = HashMap::new();
+
+        for (ligature, expansion) in LATIN_LIGATURES.iter().chain(CJK_PRESENTATION_FORMS) {
+            table.insert(*ligature, expansion.to_string());
+        }
+
+        let mut code_point = 0xFE80;
+        for (base, forms) in ARABIC_CONTEXTUAL_FORMS {
+            for offset in 0..*forms {
+                if let Some(form) = char::from_u32(code_point + offset) {
+                    table.insert(form, base.to_string());
+                }
+            }
+            code_point += forms;
+        }
+
+        for (code_point, expansion) in ARABIC_LAM_ALEF {
+            for offset in 0..2 {
+                if let Some(form) = char::from_u32(code_point + offset) {
+                    table.insert(form, expansion.to_string());
+                }
+            }
+        }
+
+        Self {
+            name: "LigatureProcessor".to_string(),
+            table,
+        }
+    }
+
+    /// Create a processor with an empty table, to be filled with `with_mapping`
+    pub fn empty() -> Self {
+        Self {
+            name: "LigatureProcessor".to_string(),
+            table: HashMap::new(),
+        }
+    }
+
+    /// Add or override the expansion for a character
+    pub fn with_mapping(mut self, ligature: char, expansion: &str) -> Self {
+        self.table.insert(ligature, expansion.to_string());
+        self
+    }
+
+    /// Leave a character untouched even if the default table knows it
+    pub fn without_mapping(mut self, ligature: char) -> Self {
+        self.table.remove(&ligature);
+        self
+    }
+
+    /// Expand ligatures in text, returning the new text and the number of expansions
+    fn expand(&self, text: &str) -> (String, usize) {
+        let mut result = String::with_capacity(text.len());
+        let mut expanded = 0;
+
+        for ch in text.chars() {
+            match self.table.get(&ch) {
+                Some(expansion) => {
+                    result.push_str(expansion);
+                    expanded += 1;
+                }
+                None => result.push(ch),
+            }
+        }
+
+        (result, expanded)
+    }
+}
+
+impl NodeProcessor for LigatureProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        match node {
+            Node::Text(text) => text.value.chars().any(|ch| self.table.contains_key(&ch)),
+            _ => false,
+        }
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        if let Node::Text(text) = &mut node {
+            let (expanded_text, expanded) = self.expand(&text.value);
+            text.value = expanded_text;
+            context.count(&self.name, "expanded", expanded);
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
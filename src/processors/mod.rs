@@ -2,8 +2,48 @@ pub mod traits;
 pub mod whitespace_processor;
 pub mod image_processor;
 pub mod table_processor;
+pub mod ligature_processor;
+pub mod normalization_processor;
+pub mod invisible_char_processor;
+pub mod bullet_list_processor;
+pub mod numbered_list_processor;
+pub mod aligned_table_processor;
+pub mod table_stitch_processor;
+pub mod table_caption_processor;
+pub mod html_table_processor;
+pub mod table_linearize_processor;
+pub mod image_asset_processor;
+pub mod image_check_processor;
+pub mod data_uri_image_processor;
+pub mod repeated_image_processor;
+pub mod figure_caption_processor;
+pub mod cjk_spacing_processor;
+pub mod punctuation_processor;
+pub mod ruby_processor;
+pub mod vertical_text_processor;
+pub(crate) mod paragraph_lines;
+pub mod text_spans;
 
 pub use traits::{NodeProcessor, ProcessContext};
-pub use whitespace_processor::WhitespaceProcessor;
+pub use whitespace_processor::{WhitespaceProcessor, CharClass, LanguagePreset};
 pub use image_processor::ImageProcessor;
-pub use table_processor::TableProcessor;
\ No newline at end of file
+pub use table_processor::TableProcessor;
+pub use ligature_processor::LigatureProcessor;
+pub use normalization_processor::{NormalizationProcessor, NormalizationPolicy};
+pub use invisible_char_processor::{InvisibleCharProcessor, InvisibleCategory, InvisibleAction};
+pub use bullet_list_processor::BulletListProcessor;
+pub use numbered_list_processor::{NumberedListProcessor, Enumerator, EnumeratorKind};
+pub use aligned_table_processor::AlignedTableProcessor;
+pub use table_stitch_processor::TableStitchProcessor;
+pub use table_caption_processor::TableCaptionProcessor;
+pub use html_table_processor::HtmlTableProcessor;
+pub use table_linearize_processor::{TableLinearizeProcessor, TableLinearization};
+pub use image_asset_processor::ImageAssetProcessor;
+pub use image_check_processor::ImageCheckProcessor;
+pub use data_uri_image_processor::DataUriImageProcessor;
+pub use repeated_image_processor::RepeatedImageProcessor;
+pub use figure_caption_processor::FigureCaptionProcessor;
+pub use cjk_spacing_processor::{CjkSpacingProcessor, SpacingPolicy};
+pub use punctuation_processor::{PunctuationProcessor, PunctuationStyle};
+pub use ruby_processor::{RubyProcessor, RubyOutput};
+pub use vertical_text_processor::VerticalTextProcessor;
\ No newline at end of file
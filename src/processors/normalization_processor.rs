@@ -0,0 +1,123 @@
+use anyhow::Result;
+use markdown::mdast::Node;
+use unicode_normalization::UnicodeNormalization;
+use super::text_spans::map_unprotected;
+use super::traits::{NodeProcessor, ProcessContext};
+
+/// Normalization applied by `NormalizationProcessor`
+/// Policies are applied in the order they were added
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormalizationPolicy {
+    /// Unicode NFC: canonical composition (decomposed kana and accented Latin letters)
+    Nfc,
+    /// Unicode NFKC: compatibility decomposition followed by canonical composition
+    /// (`ｶﾞ`, `①`, `㈱`, `㌔`, ligatures)
+    Nfkc,
+    /// Full-width ASCII (`ＡＢＣ１２３`) to half-width (`ABC123`)
+    FullwidthAsciiToHalfwidth,
+    /// Half-width katakana (`ｶﾀｶﾅ`) to full-width (`カタカナ`)
+    HalfwidthKatakanaToFullwidth,
+}
+
+const COMBINING_VOICED: char = '\u{3099}';
+const COMBINING_SEMI_VOICED: char = '\u{309A}';
+
+/// Half-width katakana block (U+FF61..U+FF9D) in code point order
+const HALFWIDTH_KATAKANA: &str = "。「」、・ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン";
+
+/// Unicode normalization processor
+/// Japanese PDFs mix full-width alphanumerics, half-width katakana and decomposed
+/// characters; this normalizes Text nodes with the selected policies.
+/// Code is never a Text node, and URLs and `$...$` math inside text are skipped.
+pub struct NormalizationProcessor {
+    name: String,
+    policies: Vec<NormalizationPolicy>,
+}
+
+impl Default for NormalizationProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NormalizationProcessor {
+    /// Create a processor applying NFC only
+    pub fn new() -> Self {
+        Self::with_policies(&[NormalizationPolicy::Nfc])
+    }
+
+    pub fn with_policies(policies: &[NormalizationPolicy]) -> Self {
+        Self {
+            name: "NormalizationProcessor".to_string(),
+            policies: policies.to_vec(),
+        }
+    }
+
+    /// Append a policy to the ones already configured
+    pub fn with_policy(mut self, policy: NormalizationPolicy) -> Self {
+        if !self.policies.contains(&policy) {
+            self.policies.push(policy);
+        }
+        self
+    }
+
+    pub fn policies(&self) -> &[NormalizationPolicy] {
+        &self.policies
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        self.policies.iter().fold(text.to_string(), |text, policy| match policy {
+            NormalizationPolicy::Nfc => text.nfc().collect(),
+            NormalizationPolicy::Nfkc => text.nfkc().collect(),
+            NormalizationPolicy::FullwidthAsciiToHalfwidth => {
+                text.chars().map(|ch| fullwidth_ascii_to_halfwidth(ch).unwrap_or(ch)).collect()
+            }
+            NormalizationPolicy::HalfwidthKatakanaToFullwidth => {
+                let widened: String = text.chars()
+                    .map(|ch| halfwidth_katakana_to_fullwidth(ch).unwrap_or(ch))
+                    .collect();
+                // Marks that did not combine with a preceding kana become spacing marks
+                widened.nfc().collect::<String>()
+                    .replace(COMBINING_VOICED, "\u{309B}")
+                    .replace(COMBINING_SEMI_VOICED, "\u{309C}")
+            }
+        })
+    }
+}
+
+fn fullwidth_ascii_to_halfwidth(ch: char) -> Option<char> {
+    match ch as u32 {
+        0xFF01..=0xFF5E => char::from_u32(ch as u32 - 0xFF01 + 0x21),
+        _ => None,
+    }
+}
+
+fn halfwidth_katakana_to_fullwidth(ch: char) -> Option<char> {
+    match ch as u32 {
+        0xFF61..=0xFF9D => HALFWIDTH_KATAKANA.chars().nth((ch as u32 - 0xFF61) as usize),
+        0xFF9E => Some(COMBINING_VOICED),
+        0xFF9F => Some(COMBINING_SEMI_VOICED),
+        _ => None,
+    }
+}
+
+impl NodeProcessor for NormalizationProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        matches!(node, Node::Text(_)) && !self.policies.is_empty()
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        if let Node::Text(text) = &mut node {
+            let normalized = map_unprotected(&text.value, |span| self.normalize(span));
+            if normalized != text.value {
+                text.value = normalized;
+                context.count(&self.name, "normalized", 1);
+            }
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
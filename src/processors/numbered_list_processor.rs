@@ -0,0 +1,258 @@
+use std::sync::OnceLock;
+use anyhow::Result;
+use markdown::mdast::{List, ListItem, Node, Text};
+use regex::Regex;
+use super::paragraph_lines::{any_line_matches, convert_line_runs, join_lines, line_text, RunItem};
+use super::traits::{NodeProcessor, ProcessContext};
+
+/// Kind of numbering an enumerator uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumeratorKind {
+    /// `1)`, `(1)`, `１．`
+    Decimal,
+    /// `a.`, `(b)`
+    LowerAlpha,
+    /// `A.`, `(B)`
+    UpperAlpha,
+    /// `一、`, `（二）`
+    Kanji,
+    /// `①`, `⑴`, `⒈`
+    Enclosed,
+}
+
+/// A detected enumerator such as `(3)`: its kind, value and surrounding punctuation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enumerator {
+    pub kind: EnumeratorKind,
+    pub value: u32,
+    /// Punctuation around the number (e.g. `(`/`)`), normalized to half-width
+    pub prefix: String,
+    pub suffix: String,
+    /// The marker exactly as it appeared in the source
+    pub marker: String,
+}
+
+const KANJI_DIGITS: &str = "〇一二三四五六七八九";
+
+fn enumerator_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"^(?:([(（])\s*([0-9０-９]{1,3}|[A-Za-z]|[〇一二三四五六七八九十]{1,3})\s*([)）])|([0-9０-９]{1,3}|[A-Za-z]|[〇一二三四五六七八九十]{1,3})([.)．）、]))")
+            .expect("valid enumerator pattern")
+    })
+}
+
+/// Parse a kanji numeral up to 99 (`三`, `十二`, `二十一`)
+fn parse_kanji(numeral: &str) -> Option<u32> {
+    let digit = |ch: char| KANJI_DIGITS.chars().position(|d| d == ch).map(|d| d as u32);
+    let chars: Vec<char> = numeral.chars().collect();
+
+    match chars.iter().position(|&ch| ch == '十') {
+        None if chars.len() == 1 => digit(chars[0]),
+        None => None,
+        Some(ten) => {
+            let tens = if ten == 0 { 1 } else if ten == 1 { digit(chars[0])? } else { return None };
+            let ones = match &chars[ten + 1..] {
+                [] => 0,
+                [ch] => digit(*ch)?,
+                _ => return None,
+            };
+            Some(tens * 10 + ones)
+        }
+    }
+}
+
+fn half_width(text: &str) -> String {
+    text.chars()
+        .map(|ch| match ch as u32 {
+            0xFF01..=0xFF5E => char::from_u32(ch as u32 - 0xFEE0).unwrap_or(ch),
+            _ => ch,
+        })
+        .collect()
+}
+
+/// Detect an enumerator at the start of a line, returning it and its length in bytes
+pub(crate) fn detect_enumerator(line: &[Node]) -> Option<(Enumerator, usize)> {
+    let text = line_text(line)?;
+    let leading = text.len() - text.trim_start().len();
+    let rest = &text[leading..];
+
+    let (enumerator, length) = match rest.chars().next()? {
+        ch @ ('①'..='⑳' | '⑴'..='⒇' | '⒈'..='⒛') => {
+            let (base, prefix, suffix) = match ch {
+                '①'..='⑳' => ('①', "", ""),
+                '⑴'..='⒇' => ('⑴', "(", ")"),
+                _ => ('⒈', "", "."),
+            };
+            let enumerator = Enumerator {
+                kind: EnumeratorKind::Enclosed,
+                value: ch as u32 - base as u32 + 1,
+                prefix: prefix.to_string(),
+                suffix: suffix.to_string(),
+                marker: ch.to_string(),
+            };
+            (enumerator, ch.len_utf8())
+        }
+        _ => {
+            let captures = enumerator_pattern().captures(rest)?;
+            let whole = captures.get(0)?;
+            let (prefix, number, suffix) = match captures.get(2) {
+                Some(number) => (half_width(&captures[1]), number.as_str(), half_width(&captures[3])),
+                None => (String::new(), captures.get(4)?.as_str(), half_width(&captures[5])),
+            };
+
+            let first = number.chars().next()?;
+            let (kind, value) = if first.is_ascii_alphabetic() {
+                let kind = if first.is_ascii_lowercase() { EnumeratorKind::LowerAlpha } else { EnumeratorKind::UpperAlpha };
+                (kind, first.to_ascii_lowercase() as u32 - 'a' as u32 + 1)
+            } else if KANJI_DIGITS.contains(first) || first == '十' {
+                (EnumeratorKind::Kanji, parse_kanji(number)?)
+            } else {
+                (EnumeratorKind::Decimal, half_width(number).parse().ok()?)
+            };
+
+            let enumerator = Enumerator {
+                kind,
+                value,
+                prefix,
+                suffix,
+                marker: whole.as_str().to_string(),
+            };
+            (enumerator, whole.end())
+        }
+    };
+
+    let after = rest[length..].chars().next()?;
+    // `1.5`, `e.g.` and `A.Smith` are not enumerators
+    let glued_ascii = after.is_ascii_alphanumeric() && enumerator.prefix.is_empty() && enumerator.suffix == ".";
+    if glued_ascii || rest[length..].trim().is_empty() {
+        return None;
+    }
+    // Kanji numerals only enumerate with `、`/`.` or parentheses; `一)` is not used
+    if enumerator.kind == EnumeratorKind::Kanji && enumerator.prefix.is_empty() && enumerator.suffix == ")" {
+        return None;
+    }
+
+    Some((enumerator, leading + length))
+}
+
+/// Whether `next` continues a run after `previous`: same style, value incremented by one
+fn continues(previous: &Enumerator, next: &Enumerator) -> bool {
+    previous.kind == next.kind
+        && previous.prefix == next.prefix
+        && previous.suffix == next.suffix
+        && next.value == previous.value + 1
+}
+
+/// Numbered-list detection processor
+/// Japanese and English documents use enumerators CommonMark doesn't recognize
+/// (`1)` glued to text, `(1)`, `①`, `一、`, `a.`); runs of consecutively numbered
+/// paragraphs or lines with the same style become ordered lists with the right `start`.
+/// With `preserve_markers`, the list stays ordered and each item also keeps its original
+/// enumerator as text (`1. ① 準備`), since CommonMark has no syntax for those markers.
+pub struct NumberedListProcessor {
+    name: String,
+    min_items: usize,
+    preserve_markers: bool,
+}
+
+impl Default for NumberedListProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NumberedListProcessor {
+    pub fn new() -> Self {
+        Self {
+            name: "NumberedListProcessor".to_string(),
+            min_items: 2,
+            preserve_markers: false,
+        }
+    }
+
+    /// Minimum number of consecutive enumerated items required to build a list (default 2)
+    pub fn with_min_items(mut self, min_items: usize) -> Self {
+        self.min_items = min_items.max(1);
+        self
+    }
+
+    /// Keep the original markers (`①`, `(1)`, ...) at the start of each item's text
+    pub fn with_preserve_markers(mut self, preserve_markers: bool) -> Self {
+        self.preserve_markers = preserve_markers;
+        self
+    }
+
+    fn make_list(&self, items: Vec<RunItem<Enumerator>>, context: &ProcessContext) -> Node {
+        let start = items.first().map(|item| item.marker.value).unwrap_or(1);
+        context.count(&self.name, "lists", 1);
+        context.count(&self.name, "items", items.len());
+
+        let children = items.into_iter()
+            .map(|item| {
+                let mut lines = item.lines;
+                if self.preserve_markers {
+                    if let Some(first) = lines.first_mut() {
+                        first.insert(0, Node::Text(Text {
+                            value: format!("{} ", item.marker.marker),
+                            position: None,
+                        }));
+                    }
+                }
+                Node::ListItem(ListItem {
+                    children: vec![join_lines(lines)],
+                    position: None,
+                    spread: false,
+                    checked: None,
+                })
+            })
+            .collect();
+
+        Node::List(List {
+            children,
+            position: None,
+            ordered: true,
+            start: Some(start),
+            spread: false,
+        })
+    }
+
+    fn convert_children(&self, children: Vec<Node>, context: &ProcessContext) -> Vec<Node> {
+        convert_line_runs(
+            children,
+            detect_enumerator,
+            continues,
+            true,
+            true,
+            self.min_items,
+            |items| self.make_list(items, context),
+        )
+    }
+}
+
+impl NodeProcessor for NumberedListProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        match node {
+            Node::Root(root) => any_line_matches(&root.children, detect_enumerator),
+            Node::Blockquote(quote) => any_line_matches(&quote.children, detect_enumerator),
+            _ => false,
+        }
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        match &mut node {
+            Node::Root(root) => {
+                root.children = self.convert_children(std::mem::take(&mut root.children), context);
+            }
+            Node::Blockquote(quote) => {
+                quote.children = self.convert_children(std::mem::take(&mut quote.children), context);
+            }
+            _ => {}
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
@@ -0,0 +1,259 @@
+use markdown::mdast::{Node, Paragraph, Text};
+
+/// One line of a paragraph, split on soft line breaks
+#[derive(Debug, Clone)]
+pub(crate) struct Line {
+    /// Index of the paragraph this line came from
+    pub paragraph: usize,
+    pub inlines: Vec<Node>,
+}
+
+/// An item of a detected run: its marker, source indentation (when known) and content lines
+#[derive(Debug)]
+pub(crate) struct RunItem<M> {
+    pub marker: M,
+    pub indent: Option<usize>,
+    pub lines: Vec<Vec<Node>>,
+}
+
+/// Split a paragraph's inline children into lines at soft and hard line breaks
+pub(crate) fn split_lines(paragraph: &Paragraph, index: usize) -> Vec<Line> {
+    let mut lines = vec![Line { paragraph: index, inlines: Vec::new() }];
+
+    for child in &paragraph.children {
+        match child {
+            Node::Text(text) => {
+                for (n, part) in text.value.split('\n').enumerate() {
+                    if n > 0 {
+                        lines.push(Line { paragraph: index, inlines: Vec::new() });
+                    }
+                    if !part.is_empty() {
+                        let position = if n == 0 { text.position.clone() } else { None };
+                        lines.last_mut().unwrap().inlines.push(Node::Text(Text {
+                            value: part.to_string(),
+                            position,
+                        }));
+                    }
+                }
+            }
+            Node::Break(_) => lines.push(Line { paragraph: index, inlines: Vec::new() }),
+            other => lines.last_mut().unwrap().inlines.push(other.clone()),
+        }
+    }
+
+    lines.retain(|line| !line.inlines.is_empty());
+    lines
+}
+
+/// Join lines back into a single paragraph
+pub(crate) fn join_lines(lines: Vec<Vec<Node>>) -> Node {
+    let mut children: Vec<Node> = Vec::new();
+
+    for (n, inlines) in lines.into_iter().enumerate() {
+        if n > 0 {
+            match children.last_mut() {
+                Some(Node::Text(text)) => text.value.push('\n'),
+                _ => children.push(Node::Text(Text { value: "\n".to_string(), position: None })),
+            }
+        }
+        for inline in inlines {
+            match (children.last_mut(), inline) {
+                (Some(Node::Text(previous)), Node::Text(text)) => previous.value.push_str(&text.value),
+                (_, inline) => children.push(inline),
+            }
+        }
+    }
+
+    Node::Paragraph(Paragraph { children, position: None })
+}
+
+/// Text at the start of a line, if the line starts with a Text node
+pub(crate) fn line_text(line: &[Node]) -> Option<&str> {
+    match line.first() {
+        Some(Node::Text(text)) => Some(&text.value),
+        _ => None,
+    }
+}
+
+/// Plain text of a node's inline content (images as their markdown source)
+pub(crate) fn inline_text(node: &Node) -> String {
+    match node {
+        Node::Text(text) => text.value.clone(),
+        Node::InlineCode(code) => code.value.clone(),
+        Node::Image(image) => format!("![{}]({})", image.alt, image.url),
+        other => other.children()
+            .map(|children| children.iter().map(inline_text).collect())
+            .unwrap_or_default(),
+    }
+}
+
+/// Remove the first `count` bytes of text from the start of a line
+pub(crate) fn strip_line_prefix(inlines: &[Node], count: usize) -> Vec<Node> {
+    let mut stripped = inlines.to_vec();
+    if let Some(Node::Text(text)) = stripped.first_mut() {
+        text.value = text.value[count..].trim_start().to_string();
+        if text.value.is_empty() {
+            stripped.remove(0);
+        }
+    }
+    stripped
+}
+
+/// Whether any line of any paragraph among `children` is detected as a run item
+pub(crate) fn any_line_matches<M>(children: &[Node], detect: impl Fn(&[Node]) -> Option<(M, usize)>) -> bool {
+    children.iter().any(|child| match child {
+        Node::Paragraph(paragraph) => split_lines(paragraph, 0)
+            .iter()
+            .any(|line| detect(&line.inlines).is_some()),
+        _ => false,
+    })
+}
+
+/// Rewrite a container's children, replacing runs of marked lines with the node built from them
+///
+/// `detect` recognizes a marked line and returns its marker and prefix length in bytes;
+/// `continues` decides whether a marker may follow the previous item's marker in the same run.
+/// A marked line starts an item; with `continuation_lines`, following unmarked lines of the
+/// same paragraph continue it, otherwise they end the run. With `span_paragraphs` a run may go
+/// on into a following paragraph that starts with a marked line; otherwise it ends with its
+/// paragraph. Runs shorter than `min_items` are left alone, as are paragraphs no run touches.
+pub(crate) fn convert_line_runs<M>(
+    children: Vec<Node>,
+    detect: impl Fn(&[Node]) -> Option<(M, usize)>,
+    continues: impl Fn(&M, &M) -> bool,
+    continuation_lines: bool,
+    span_paragraphs: bool,
+    min_items: usize,
+    mut build: impl FnMut(Vec<RunItem<M>>) -> Node,
+) -> Vec<Node> {
+    enum Entry {
+        Line(Line, Option<usize>),
+        Block(Node),
+    }
+
+    // Flatten paragraphs into lines; the first line of a paragraph knows its indentation
+    let mut entries = Vec::new();
+    let mut paragraphs = Vec::new();
+    for child in children {
+        match child {
+            Node::Paragraph(paragraph) => {
+                let index = paragraphs.len();
+                let indent = paragraph.position.as_ref().map(|p| p.start.column - 1);
+                for (n, line) in split_lines(&paragraph, index).into_iter().enumerate() {
+                    entries.push(Entry::Line(line, if n == 0 { indent } else { None }));
+                }
+                paragraphs.push(Some(Node::Paragraph(paragraph)));
+            }
+            other => entries.push(Entry::Block(other)),
+        }
+    }
+
+    // Find runs, numbering them so adjacent runs stay separate
+    let mut run_of: Vec<Option<usize>> = vec![None; entries.len()];
+    let mut touched = vec![false; paragraphs.len()];
+    let mut run_count = 0;
+    let mut i = 0;
+    while i < entries.len() {
+        let first = match &entries[i] {
+            Entry::Line(line, _) => detect(&line.inlines).map(|(marker, _)| (marker, line.paragraph)),
+            Entry::Block(_) => None,
+        };
+        let Some((mut previous, paragraph)) = first else {
+            i += 1;
+            continue;
+        };
+
+        let mut j = i + 1;
+        let mut item_count = 1;
+        while let Some(Entry::Line(line, _)) = entries.get(j) {
+            if !span_paragraphs && line.paragraph != paragraph {
+                break;
+            }
+            match detect(&line.inlines) {
+                Some((marker, _)) if continues(&previous, &marker) => {
+                    previous = marker;
+                    item_count += 1;
+                }
+                Some(_) => break,
+                None if continuation_lines
+                    && matches!(&entries[j - 1], Entry::Line(prev, _) if prev.paragraph == line.paragraph) => {}
+                None => break,
+            }
+            j += 1;
+        }
+
+        if item_count >= min_items {
+            for (entry, run) in entries[i..j].iter().zip(&mut run_of[i..j]) {
+                *run = Some(run_count);
+                // Paragraphs touched by a run are rebuilt from their lines
+                if let Entry::Line(line, _) = entry {
+                    touched[line.paragraph] = true;
+                }
+            }
+            run_count += 1;
+            i = j;
+        } else {
+            i += 1;
+        }
+    }
+
+    // Rebuild the children
+    let mut result = Vec::new();
+    let mut items: Vec<RunItem<M>> = Vec::new();
+    let mut current_run = None;
+    let mut loose_lines: Vec<Line> = Vec::new();
+
+    let flush_loose = |loose_lines: &mut Vec<Line>, result: &mut Vec<Node>| {
+        if !loose_lines.is_empty() {
+            result.push(join_lines(loose_lines.drain(..).map(|line| line.inlines).collect()));
+        }
+    };
+
+    for (entry, run) in entries.into_iter().zip(run_of) {
+        if run != current_run && !items.is_empty() {
+            result.push(build(std::mem::take(&mut items)));
+        }
+        current_run = run;
+
+        match entry {
+            Entry::Line(line, indent) if run.is_some() => {
+                flush_loose(&mut loose_lines, &mut result);
+                match detect(&line.inlines) {
+                    Some((marker, prefix_len)) => {
+                        items.push(RunItem {
+                            marker,
+                            indent,
+                            lines: vec![strip_line_prefix(&line.inlines, prefix_len)],
+                        });
+                    }
+                    None => items.last_mut().unwrap().lines.push(line.inlines),
+                }
+            }
+            Entry::Line(line, _) => {
+                if !touched[line.paragraph] {
+                    // Untouched paragraph: keep the original node (and its position) once
+                    if let Some(paragraph) = paragraphs[line.paragraph].take() {
+                        flush_loose(&mut loose_lines, &mut result);
+                        result.push(paragraph);
+                    }
+                    continue;
+                }
+                if loose_lines.last().is_some_and(|last| last.paragraph != line.paragraph) {
+                    flush_loose(&mut loose_lines, &mut result);
+                }
+                loose_lines.push(line);
+            }
+            Entry::Block(node) => {
+                flush_loose(&mut loose_lines, &mut result);
+                result.push(node);
+            }
+        }
+    }
+
+    flush_loose(&mut loose_lines, &mut result);
+    if !items.is_empty() {
+        result.push(build(items));
+    }
+
+    result
+}
\ No newline at end of file
@@ -0,0 +1,193 @@
+use anyhow::Result;
+use markdown::mdast::Node;
+use super::text_spans::map_unprotected;
+use super::traits::{NodeProcessor, ProcessContext};
+use super::whitespace_processor::CharClass;
+
+/// House style applied by `PunctuationProcessor`
+/// Every style turns `～` and `~` in ranges into the wave dash `〜` and em dashes or box-drawing
+/// lines next to Japanese text into `―`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PunctuationStyle {
+    /// `、。` with full-width parentheses `（）`
+    Jis,
+    /// `，．` with full-width parentheses, as in papers and official documents
+    Academic,
+    /// `、。` with half-width parentheses `()`
+    HalfwidthParentheses,
+}
+
+impl PunctuationStyle {
+    fn comma(&self) -> char {
+        match self {
+            PunctuationStyle::Academic => '，',
+            PunctuationStyle::Jis | PunctuationStyle::HalfwidthParentheses => '、',
+        }
+    }
+
+    fn period(&self) -> char {
+        match self {
+            PunctuationStyle::Academic => '．',
+            PunctuationStyle::Jis | PunctuationStyle::HalfwidthParentheses => '。',
+        }
+    }
+}
+
+/// Characters the processor may rewrite
+const CANDIDATES: &str = "、，､,。．｡.～~—─━()（）";
+
+const DASH: char = '―';
+const WAVE_DASH: char = '〜';
+
+fn is_japanese(ch: char) -> bool {
+    CharClass::Han.contains(ch) || CharClass::Kana.contains(ch)
+}
+
+fn is_digit(ch: char) -> bool {
+    ch.is_ascii_digit() || ('０'..='９').contains(&ch)
+}
+
+fn is_dash(ch: char) -> bool {
+    matches!(ch, '—' | '─' | '━' | DASH)
+}
+
+/// Japanese punctuation normalization processor
+/// Extracted documents mix `、。` with `，．`, full-width with half-width parentheses, `~` with
+/// `〜` and assorted dash forms. Punctuation in Text nodes is rewritten to one house style;
+/// ASCII marks are only touched next to Japanese text and separators inside numbers
+/// (`3．14`, `1，000`) are kept. URLs, e-mail addresses and math are left alone.
+pub struct PunctuationProcessor {
+    name: String,
+    style: PunctuationStyle,
+}
+
+impl Default for PunctuationProcessor {
+    fn default() -> Self {
+        Self::new(PunctuationStyle::Jis)
+    }
+}
+
+impl PunctuationProcessor {
+    pub fn new(style: PunctuationStyle) -> Self {
+        Self {
+            name: "PunctuationProcessor".to_string(),
+            style,
+        }
+    }
+
+    pub fn style(&self) -> PunctuationStyle {
+        self.style
+    }
+
+    fn normalize(&self, text: &str, context: &ProcessContext) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let mut output = chars.clone();
+
+        for (index, &ch) in chars.iter().enumerate() {
+            let previous = index.checked_sub(1).map(|previous| chars[previous]);
+            let next = chars.get(index + 1).copied();
+            let in_number = previous.is_some_and(is_digit) && next.is_some_and(is_digit);
+            let after_japanese = previous.is_some_and(is_japanese);
+
+            let replacement = match ch {
+                '、' | '､' => Some((self.style.comma(), "commas")),
+                '，' if !in_number => Some((self.style.comma(), "commas")),
+                ',' if after_japanese && !next.is_some_and(is_digit) => Some((self.style.comma(), "commas")),
+                '。' | '｡' => Some((self.style.period(), "periods")),
+                '．' if !in_number => Some((self.style.period(), "periods")),
+                // Not in ellipses, abbreviations or version numbers
+                '.' if after_japanese && !next.is_some_and(|next| next == '.' || next.is_ascii_alphanumeric()) => {
+                    Some((self.style.period(), "periods"))
+                }
+                '～' => Some((WAVE_DASH, "wave_dashes")),
+                '~' if previous.is_some_and(|c| is_digit(c) || is_japanese(c)) && next.is_some_and(|c| is_digit(c) || is_japanese(c)) => {
+                    Some((WAVE_DASH, "wave_dashes"))
+                }
+                _ => None,
+            };
+            if let Some((replacement, counter)) = replacement.filter(|(replacement, _)| *replacement != ch) {
+                output[index] = replacement;
+                context.count(&self.name, counter, 1);
+            }
+        }
+
+        self.normalize_dashes(&mut output, context);
+        self.normalize_parentheses(&mut output, context);
+        output.into_iter().collect()
+    }
+
+    /// Whole runs of dashes touching Japanese text become `―`; rules such as `────` on a
+    /// line of their own are left alone
+    fn normalize_dashes(&self, chars: &mut [char], context: &ProcessContext) {
+        let mut start = 0;
+        while start < chars.len() {
+            if !is_dash(chars[start]) {
+                start += 1;
+                continue;
+            }
+            let end = chars[start..].iter().position(|&ch| !is_dash(ch)).map_or(chars.len(), |length| start + length);
+            let touches_japanese = start.checked_sub(1).is_some_and(|previous| is_japanese(chars[previous]))
+                || chars.get(end).is_some_and(|&next| is_japanese(next));
+            if touches_japanese {
+                for ch in chars[start..end].iter_mut().filter(|ch| **ch != DASH) {
+                    *ch = DASH;
+                    context.count(&self.name, "dashes", 1);
+                }
+            }
+            start = end;
+        }
+    }
+
+    fn normalize_parentheses(&self, chars: &mut [char], context: &ProcessContext) {
+        if self.style == PunctuationStyle::HalfwidthParentheses {
+            for ch in chars.iter_mut() {
+                let replacement = match *ch {
+                    '（' => '(',
+                    '）' => ')',
+                    _ => continue,
+                };
+                *ch = replacement;
+                context.count(&self.name, "parentheses", 1);
+            }
+            return;
+        }
+
+        // Half-width pairs enclosing or following Japanese text become full-width; `f(x)` stays
+        let mut open = Vec::new();
+        for index in 0..chars.len() {
+            match chars[index] {
+                '(' => open.push(index),
+                ')' => {
+                    let Some(start) = open.pop() else {
+                        continue;
+                    };
+                    let japanese = chars[start + 1..index].iter().any(|&ch| is_japanese(ch))
+                        || start.checked_sub(1).is_some_and(|previous| is_japanese(chars[previous]));
+                    if japanese {
+                        chars[start] = '（';
+                        chars[index] = '）';
+                        context.count(&self.name, "parentheses", 2);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl NodeProcessor for PunctuationProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        matches!(node, Node::Text(text) if text.value.contains(|ch| CANDIDATES.contains(ch)))
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        if let Node::Text(text) = &mut node {
+            text.value = map_unprotected(&text.value, |span| self.normalize(span, context));
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
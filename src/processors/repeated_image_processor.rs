@@ -0,0 +1,185 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use anyhow::Result;
+use markdown::mdast::Node;
+use super::data_uri_image_processor::content_hash;
+use super::image_asset_processor::{is_relative_local, percent_decode};
+use super::traits::{NodeProcessor, ProcessContext};
+
+/// Whether a paragraph holds nothing but whitespace and line breaks
+fn is_blank(node: &Node) -> bool {
+    node.children().is_some_and(|children| {
+        children.iter().all(|child| match child {
+            Node::Text(text) => text.value.trim().is_empty(),
+            Node::Break(_) => true,
+            _ => false,
+        })
+    })
+}
+
+/// Identities of the images of a document, cached per URL
+#[derive(Default)]
+struct ImageKeys {
+    keys: HashMap<String, String>,
+    /// Distinct contents seen per content hash, so a hash collision never merges two images
+    contents: HashMap<u64, Vec<Vec<u8>>>,
+}
+
+impl ImageKeys {
+    /// Identity of an image: the content of a readable local file, otherwise its URL
+    fn key(&mut self, url: &str, input_dir: &Path) -> String {
+        let url = url.trim();
+        if let Some(key) = self.keys.get(url) {
+            return key.clone();
+        }
+        let content = is_relative_local(url)
+            .then(|| fs::read(input_dir.join(percent_decode(url))).ok())
+            .flatten();
+        let key = match content {
+            Some(content) => {
+                let hash = content_hash(&content);
+                let seen = self.contents.entry(hash).or_default();
+                let index = seen.iter().position(|other| *other == content).unwrap_or_else(|| {
+                    seen.push(content);
+                    seen.len() - 1
+                });
+                format!("content:{:016x}:{}", hash, index)
+            }
+            None => format!("url:{}", url),
+        };
+        self.keys.insert(url.to_string(), key.clone());
+        key
+    }
+}
+
+/// Repeated decorative image removal processor
+/// Page headers and footers repeat the same logo on every page of an extracted document.
+/// Images whose URL, or content for local files, occurs more than `threshold` times are
+/// treated as decorative: all occurrences but the first are removed (or all of them), and
+/// paragraphs left empty are dropped. URLs containing an allowlisted pattern are never removed.
+pub struct RepeatedImageProcessor {
+    name: String,
+    threshold: usize,
+    keep_first: bool,
+    allowlist: Vec<String>,
+}
+
+impl Default for RepeatedImageProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RepeatedImageProcessor {
+    pub fn new() -> Self {
+        Self {
+            name: "RepeatedImageProcessor".to_string(),
+            threshold: 2,
+            keep_first: true,
+            allowlist: Vec::new(),
+        }
+    }
+
+    /// An image occurring more than `threshold` times is decorative (default 2)
+    pub fn with_threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold.max(1);
+        self
+    }
+
+    /// Keep the first occurrence of a decorative image (default true)
+    pub fn with_keep_first(mut self, keep_first: bool) -> Self {
+        self.keep_first = keep_first;
+        self
+    }
+
+    /// Never remove images whose URL contains `pattern`
+    pub fn with_allowed(mut self, pattern: impl Into<String>) -> Self {
+        self.allowlist.push(pattern.into());
+        self
+    }
+
+    fn count_images(node: &Node, input_dir: &Path, keys: &mut ImageKeys, counts: &mut HashMap<String, (usize, String)>) {
+        if let Node::Image(image) = node {
+            let key = keys.key(&image.url, input_dir);
+            counts.entry(key).or_insert((0, image.url.clone())).0 += 1;
+        }
+        for child in node.children().into_iter().flatten() {
+            Self::count_images(child, input_dir, keys, counts);
+        }
+    }
+
+    fn remove_images(
+        &self,
+        children: &mut Vec<Node>,
+        decorative: &HashSet<String>,
+        input_dir: &Path,
+        keys: &mut ImageKeys,
+        seen: &mut HashSet<String>,
+    ) {
+        let mut kept = Vec::with_capacity(children.len());
+        for mut child in children.drain(..) {
+            if let Node::Image(image) = &child {
+                let key = keys.key(&image.url, input_dir);
+                let first = seen.insert(key.clone());
+                if decorative.contains(&key) && !(self.keep_first && first) {
+                    continue;
+                }
+                kept.push(child);
+                continue;
+            }
+
+            let had_content = child.children().is_some_and(|grandchildren| !grandchildren.is_empty());
+            if let Some(grandchildren) = child.children_mut() {
+                self.remove_images(grandchildren, decorative, input_dir, keys, seen);
+            }
+            // Drop paragraphs that only held removed images
+            if had_content && matches!(child, Node::Paragraph(_)) && is_blank(&child) {
+                continue;
+            }
+            kept.push(child);
+        }
+        *children = kept;
+    }
+}
+
+impl NodeProcessor for RepeatedImageProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        matches!(node, Node::Root(_))
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        let input_dir = context.file_path.as_deref()
+            .and_then(|path| Path::new(path).parent())
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        let mut keys = ImageKeys::default();
+        let mut counts = HashMap::new();
+        Self::count_images(&node, &input_dir, &mut keys, &mut counts);
+
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_by(|a, b| a.1.1.cmp(&b.1.1));
+
+        let mut decorative = HashSet::new();
+        for (key, (count, url)) in counts {
+            if count <= self.threshold || self.allowlist.iter().any(|pattern| url.contains(pattern.as_str())) {
+                continue;
+            }
+            let removed = if self.keep_first { count - 1 } else { count };
+            context.count(&self.name, "removed", removed);
+            context.record(&self.name, format!("image `{}` repeated {} times; removed {}", url, count, removed), None);
+            decorative.insert(key);
+        }
+
+        if !decorative.is_empty() {
+            if let Some(children) = node.children_mut() {
+                self.remove_images(children, &decorative, &input_dir, &mut keys, &mut HashSet::new());
+            }
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
@@ -0,0 +1,356 @@
+use std::sync::OnceLock;
+use anyhow::Result;
+use markdown::mdast::{Node, Text};
+use markdown::unist::{Point, Position};
+use regex::{Captures, Regex};
+use super::traits::{NodeProcessor, ProcessContext};
+use super::whitespace_processor::CharClass;
+
+/// Unicode interlinear annotation marks, used to tag detected ruby before rendering
+const ANCHOR: char = '\u{FFF9}';
+const SEPARATOR: char = '\u{FFFA}';
+const TERMINATOR: char = '\u{FFFB}';
+
+/// Longest reading accepted for a ruby line or a parenthesized reading
+const MAX_READING_CHARS: usize = 20;
+
+/// How detected ruby (furigana) is written back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RubyOutput {
+    /// Drop the readings: `漢字`
+    Strip,
+    /// `<ruby>漢<rt>かん</rt>字<rt>じ</rt></ruby>`
+    Html,
+    /// `漢字（かんじ）`
+    Parentheses,
+}
+
+fn paren_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"(\p{Han}+)[（(](\p{Hiragana}[\p{Hiragana}ー]*)[）)]").expect("valid ruby pattern"))
+}
+
+fn han_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\p{Han}+").expect("valid han pattern"))
+}
+
+fn annotation_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new("(?:\u{FFF9}[^\u{FFFA}]*\u{FFFA}[^\u{FFFB}]*\u{FFFB})+").expect("valid annotation pattern")
+    })
+}
+
+fn annotate(base: &str, reading: &str) -> String {
+    format!("{}{}{}{}{}", ANCHOR, base, SEPARATOR, reading, TERMINATOR)
+}
+
+/// Most kana a single kanji is read with (`承る` is `うけたまわ`)
+const MAX_KANA_PER_KANJI: usize = 5;
+
+/// A parenthesized reading confirmed as ruby: `text[start..end]` is `<prefix><base>(<reading>)`
+/// and `text[base_start..]` starts with `base`
+struct ParenReading {
+    start: usize,
+    base_start: usize,
+    end: usize,
+    base: String,
+    reading: String,
+}
+
+/// Parenthesized readings of a text that are ruby rather than remarks in hiragana: runs of
+/// per-kanji readings (`漢(かん)字(じ)`), and readings already known for the kanji before them
+/// from ruby lines, `with_reading` or such runs. A lone `削除(しない)` or `備考(なし)` reads
+/// just like a remark, so it is left alone.
+fn paren_readings(text: &str, known: &[(String, String)]) -> Vec<ParenReading> {
+    let candidates: Vec<(usize, usize, &str, &str)> = paren_pattern()
+        .captures_iter(text)
+        .filter_map(|captures| {
+            let (matched, run, reading) = (captures.get(0)?, captures.get(1)?, captures.get(2)?);
+            Some((matched.start(), matched.end(), run.as_str(), reading.as_str()))
+        })
+        .filter(|(_, _, _, reading)| reading.chars().count() <= MAX_READING_CHARS)
+        .collect();
+
+    // A candidate chains to the previous one when its single kanji follows it directly
+    let per_kanji = |reading: &str| reading.chars().count() <= MAX_KANA_PER_KANJI;
+    let chained = |index: usize| {
+        let (start, _, run, reading) = candidates[index];
+        index > 0
+            && candidates[index - 1].1 == start
+            && run.chars().count() == 1
+            && per_kanji(reading)
+            && per_kanji(candidates[index - 1].3)
+    };
+
+    let mut confirmed = Vec::new();
+    for (index, &(start, end, run, reading)) in candidates.iter().enumerate() {
+        let in_run = chained(index) || (index + 1 < candidates.len() && chained(index + 1));
+        let base = if in_run {
+            run.chars().last().map(String::from)
+        } else {
+            known.iter()
+                .filter(|(base, known_reading)| known_reading == reading && run.ends_with(base.as_str()))
+                .map(|(base, _)| base.clone())
+                .max_by_key(|base| base.len())
+        };
+        if let Some(base) = base {
+            confirmed.push(ParenReading {
+                start,
+                base_start: start + run.len() - base.len(),
+                end,
+                base,
+                reading: reading.to_string(),
+            });
+        }
+    }
+    confirmed
+}
+
+/// A line of kana only, as ruby extracted onto its own line above the annotated text: readings
+/// separated by spaces, or a single reading about as long as the kanji below (`とうきょう`
+/// over `東京`), so an ordinary kana sentence (`そうです`) is not taken for ruby
+fn is_ruby_line(line: &str, next: &str) -> bool {
+    let reading: Vec<char> = line.chars().filter(|ch| !ch.is_whitespace()).collect();
+    let kanji = next.chars().filter(|&ch| CharClass::Han.contains(ch)).count();
+    let groups = line.split_whitespace().count();
+    !reading.is_empty()
+        && reading.len() <= MAX_READING_CHARS
+        && reading.iter().all(|&ch| CharClass::Kana.contains(ch))
+        && reading.len() <= kanji * MAX_KANA_PER_KANJI
+        && (groups > 1 || reading.len() <= kanji * 2 + 1)
+}
+
+/// Readings of a ruby line matched to the kanji runs (or single kanji) of the next line,
+/// as byte ranges of `next` with their reading
+fn line_readings(ruby: &str, next: &str) -> Option<Vec<(usize, usize, String)>> {
+    let groups: Vec<&str> = ruby.split_whitespace().collect();
+    let runs: Vec<(usize, usize)> = han_pattern().find_iter(next).map(|found| (found.start(), found.end())).collect();
+    let kanji: Vec<(usize, usize)> = next.char_indices()
+        .filter(|(_, ch)| CharClass::Han.contains(*ch))
+        .map(|(index, ch)| (index, index + ch.len_utf8()))
+        .collect();
+
+    let targets = if groups.len() == runs.len() {
+        runs
+    } else if groups.len() == kanji.len() {
+        kanji
+    } else {
+        return None;
+    };
+    let annotations: Vec<(usize, usize, String)> = targets.into_iter()
+        .zip(groups)
+        .map(|((start, end), reading)| (start, end, reading.to_string()))
+        .collect();
+    // Every kanji is read with at least one kana
+    let plausible = annotations.iter().all(|(start, end, reading)| {
+        let kanji = next[*start..*end].chars().count();
+        (kanji..=kanji * MAX_KANA_PER_KANJI).contains(&reading.chars().count())
+    });
+    plausible.then_some(annotations)
+}
+
+fn for_each_text(node: &mut Node, visit: &mut impl FnMut(&mut Text)) {
+    match node {
+        Node::Text(text) => visit(text),
+        other => {
+            if let Some(children) = other.children_mut() {
+                for child in children {
+                    for_each_text(child, visit);
+                }
+            }
+        }
+    }
+}
+
+/// Furigana (ruby) artifact processor
+/// Ruby annotations in Japanese PDFs extract as readings in parentheses (`漢(かん)字(じ)`), as
+/// short kana lines above the annotated line, or glued to the base (`漢字かんじ`). Per-kanji
+/// parentheses and ruby lines are detected directly; a single word's parenthesized kana or a
+/// glued reading cannot be told from a remark or okurigana, so only readings seen in those
+/// forms or configured with `with_reading` are matched. Detected ruby is stripped or rewritten
+/// as `<ruby>` markup or `漢字（かんじ）`.
+pub struct RubyProcessor {
+    name: String,
+    output: RubyOutput,
+    readings: Vec<(String, String)>,
+}
+
+impl Default for RubyProcessor {
+    fn default() -> Self {
+        Self::new(RubyOutput::Strip)
+    }
+}
+
+impl RubyProcessor {
+    pub fn new(output: RubyOutput) -> Self {
+        Self {
+            name: "RubyProcessor".to_string(),
+            output,
+            readings: Vec::new(),
+        }
+    }
+
+    /// Known reading of `base`, used to find glued `<base><reading>` artifacts
+    pub fn with_reading(mut self, base: impl Into<String>, reading: impl Into<String>) -> Self {
+        self.readings.push((base.into(), reading.into()));
+        self
+    }
+
+    /// Readings confirmed in parentheses of a text
+    fn learn_parentheses(&self, text: &str, known: &[(String, String)], readings: &mut Vec<(String, String)>) {
+        // Adjacent per-kanji readings (`漢(かん)字(じ)`) also teach the whole word
+        let mut word: Option<(usize, String, String)> = None;
+        for found in paren_readings(text, known) {
+            readings.push((found.base.clone(), found.reading.clone()));
+            word = match word {
+                Some((word_end, word_base, word_reading)) if word_end == found.start && found.base_start == found.start => {
+                    Some((found.end, word_base + &found.base, word_reading + &found.reading))
+                }
+                previous => {
+                    readings.extend(previous.map(|(_, base, reading)| (base, reading)));
+                    Some((found.end, found.base, found.reading))
+                }
+            };
+        }
+        readings.extend(word.map(|(_, base, reading)| (base, reading)));
+    }
+
+    /// Readings found in ruby lines of a text
+    fn learn_ruby_lines(&self, text: &str, readings: &mut Vec<(String, String)>) {
+        let lines: Vec<&str> = text.split('\n').collect();
+        for pair in lines.windows(2) {
+            if !is_ruby_line(pair[0], pair[1]) {
+                continue;
+            }
+            for (start, end, reading) in line_readings(pair[0], pair[1]).into_iter().flatten() {
+                readings.push((pair[1][start..end].to_string(), reading));
+            }
+        }
+    }
+
+    /// Tag every detected ruby of a text with interlinear annotation marks; `known` confirms
+    /// parenthesized readings, `glued` lists the readings to find glued to their base
+    fn mark(
+        &self,
+        text: &str,
+        start: Option<&Point>,
+        known: &[(String, String)],
+        glued: &[(String, String)],
+        context: &ProcessContext,
+    ) -> String {
+        let lines: Vec<&str> = text.split('\n').collect();
+        let mut marked = Vec::with_capacity(lines.len());
+        let mut index = 0;
+        while index < lines.len() {
+            let line = lines[index];
+            // Readings that do not line up with the kanji below leave both lines as they are
+            let Some((next, annotations)) = lines.get(index + 1)
+                .filter(|next| is_ruby_line(line, next))
+                .and_then(|next| line_readings(line, next).map(|annotations| (next, annotations)))
+            else {
+                marked.push(line.to_string());
+                index += 1;
+                continue;
+            };
+
+            let position = start.map(|start| {
+                let offset = start.offset + lines[..index].iter().map(|line| line.len() + 1).sum::<usize>();
+                let column = if index == 0 { start.column } else { 1 };
+                let line_start = Point::new(start.line + index, column, offset);
+                let line_end = Point::new(start.line + index, column + line.len(), offset + line.len());
+                Position { start: line_start, end: line_end }
+            });
+            let mut annotated = String::with_capacity(next.len() * 2);
+            let mut last = 0;
+            for (from, to, reading) in &annotations {
+                annotated.push_str(&next[last..*from]);
+                annotated.push_str(&annotate(&next[*from..*to], reading));
+                last = *to;
+            }
+            annotated.push_str(&next[last..]);
+            marked.push(annotated);
+            context.count(&self.name, "ruby_lines", 1);
+            context.count(&self.name, "annotations", annotations.len());
+            context.record(&self.name, format!("ruby line \"{}\" attached to the next line", line.trim()), position.as_ref());
+            index += 2;
+        }
+        let marked = marked.join("\n");
+
+        let mut text = String::with_capacity(marked.len());
+        let mut last = 0;
+        for found in paren_readings(&marked, known) {
+            text.push_str(&marked[last..found.base_start]);
+            text.push_str(&annotate(&found.base, &found.reading));
+            context.count(&self.name, "annotations", 1);
+            last = found.end;
+        }
+        text.push_str(&marked[last..]);
+
+        for (base, reading) in glued {
+            let glued = format!("{}{}", base, reading);
+            let occurrences = text.matches(&glued).count();
+            if occurrences > 0 {
+                context.count(&self.name, "annotations", occurrences);
+                text = text.replace(&glued, &annotate(base, reading));
+            }
+        }
+        text
+    }
+
+    /// Write tagged ruby in the configured output form, one group per run of annotations
+    fn render(&self, text: &str) -> String {
+        annotation_pattern()
+            .replace_all(text, |captures: &Captures| {
+                let pairs: Vec<(&str, &str)> = captures[0]
+                    .split(TERMINATOR)
+                    .filter_map(|annotation| annotation.strip_prefix(ANCHOR)?.split_once(SEPARATOR))
+                    .collect();
+                let bases: String = pairs.iter().map(|(base, _)| *base).collect();
+                match self.output {
+                    RubyOutput::Strip => bases,
+                    RubyOutput::Html => {
+                        let inner: String = pairs.iter().map(|(base, reading)| format!("{}<rt>{}</rt>", base, reading)).collect();
+                        format!("<ruby>{}</ruby>", inner)
+                    }
+                    RubyOutput::Parentheses => {
+                        format!("{}（{}）", bases, pairs.iter().map(|(_, reading)| *reading).collect::<String>())
+                    }
+                }
+            })
+            .into_owned()
+    }
+}
+
+impl NodeProcessor for RubyProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        matches!(node, Node::Root(_))
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        let mut known = self.readings.clone();
+        for_each_text(&mut node, &mut |text| self.learn_ruby_lines(&text.value, &mut known));
+        let confirmed = known.clone();
+        for_each_text(&mut node, &mut |text| self.learn_parentheses(&text.value, &confirmed, &mut known));
+
+        // Glued readings need at least three characters to stand out from okurigana
+        let mut glued = known.clone();
+        glued.retain(|(base, reading)| reading.chars().count() >= 2 && base.chars().count() + reading.chars().count() >= 3);
+        glued.sort_by(|a, b| (b.0.len() + b.1.len()).cmp(&(a.0.len() + a.1.len())).then_with(|| a.cmp(b)));
+        glued.dedup();
+
+        for_each_text(&mut node, &mut |text| {
+            let start = text.position.as_ref().map(|position| &position.start);
+            let marked = self.mark(&text.value, start, &known, &glued, context);
+            if marked != text.value {
+                text.value = self.render(&marked);
+            }
+        });
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
@@ -0,0 +1,164 @@
+use std::sync::OnceLock;
+use anyhow::Result;
+use markdown::mdast::Node;
+use regex::Regex;
+use super::paragraph_lines::{inline_text, join_lines, split_lines};
+use super::table_processor::split_pipe_row;
+use super::traits::{NodeProcessor, ProcessContext};
+
+/// Captions longer than this are body text mentioning a table, not captions
+const MAX_CAPTION_CHARS: usize = 100;
+
+fn caption_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        // `表1 売上一覧` may use a plain space, but English captions need a delimiter or nothing
+        // after the number, or body prose such as `Table 3 shows the results` would match
+        Regex::new(r"(?x)^\s*(?:
+            表\s*[0-9０-９]+(?:[-‐.．][0-9０-９]+)*(?:\s*[:：.．—]\s*|\s+|$)
+            | (?:Table|TABLE|Tab\.)\s*[0-9０-９]+(?:[-‐.．][0-9０-９]+)*(?:\s*[:：.．—]|\s*$)
+        )").expect("valid caption pattern")
+    })
+}
+
+/// Whether a single line of text is a table caption such as `表1 売上一覧` or `Table 3: Results`
+fn is_caption_text(text: &str) -> bool {
+    let text = text.trim();
+    !text.contains('\n') && text.chars().count() <= MAX_CAPTION_CHARS && caption_pattern().is_match(text)
+}
+
+pub(crate) fn is_caption(node: &Node) -> bool {
+    matches!(node, Node::Paragraph(_)) && is_caption_text(&inline_text(node))
+}
+
+/// Tables in any form the tree may hold them: GFM tables, pipe-table paragraphs and HTML tables
+fn is_table(node: &Node) -> bool {
+    match node {
+        Node::Table(_) => true,
+        Node::Paragraph(paragraph) => {
+            let lines = split_lines(paragraph, 0);
+            lines.len() >= 2 && lines.iter().all(|line| split_pipe_row(&line.inlines).is_some())
+        }
+        Node::Html(html) => html.value.trim_start().to_lowercase().starts_with("<table"),
+        _ => false,
+    }
+}
+
+/// Table caption detection processor
+/// Captions (`表1 売上一覧`, `Table 3: Results`) end up as loose paragraphs above or below
+/// their table, sometimes glued to the table's lines. Each caption next to a table is attached
+/// to it in the report and moved directly above it, so captions always precede their table.
+pub struct TableCaptionProcessor {
+    name: String,
+}
+
+impl Default for TableCaptionProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TableCaptionProcessor {
+    pub fn new() -> Self {
+        Self {
+            name: "TableCaptionProcessor".to_string(),
+        }
+    }
+
+    /// Split caption lines glued to the first or last line of a pipe-table paragraph
+    fn split_glued_captions(children: Vec<Node>) -> Vec<Node> {
+        let mut result = Vec::new();
+        for child in children {
+            let Node::Paragraph(paragraph) = &child else {
+                result.push(child);
+                continue;
+            };
+            let lines: Vec<Vec<Node>> = split_lines(paragraph, 0).into_iter().map(|line| line.inlines).collect();
+            let is_caption_line = |line: &Vec<Node>| is_caption_text(&line.iter().map(inline_text).collect::<String>());
+            let is_row = |line: &Vec<Node>| split_pipe_row(line).is_some();
+
+            if lines.len() < 3 {
+                result.push(child);
+            } else if is_caption_line(&lines[0]) && lines[1..].iter().all(is_row) {
+                let mut lines = lines;
+                let rest = lines.split_off(1);
+                result.push(join_lines(lines));
+                result.push(join_lines(rest));
+            } else if is_caption_line(&lines[lines.len() - 1]) && lines[..lines.len() - 1].iter().all(is_row) {
+                let mut lines = lines;
+                let caption = lines.split_off(lines.len() - 1);
+                result.push(join_lines(lines));
+                result.push(join_lines(caption));
+            } else {
+                result.push(child);
+            }
+        }
+        result
+    }
+
+    fn place_captions(&self, children: Vec<Node>, context: &ProcessContext) -> Vec<Node> {
+        let mut nodes = Self::split_glued_captions(children);
+        let mut table_number = 0;
+
+        let mut i = 0;
+        while i < nodes.len() {
+            if is_table(&nodes[i]) {
+                table_number += 1;
+                i += 1;
+                continue;
+            }
+            if !is_caption(&nodes[i]) {
+                i += 1;
+                continue;
+            }
+
+            let caption = inline_text(&nodes[i]).trim().to_string();
+            let position = nodes[i].position().cloned();
+            if nodes.get(i + 1).is_some_and(is_table) {
+                // Caption above its table: already in place
+                context.count(&self.name, "captions", 1);
+                context.record(&self.name, format!("table {}: caption \"{}\"", table_number + 1, caption), position.as_ref());
+            } else if i > 0 && is_table(&nodes[i - 1]) && !(i >= 2 && is_caption(&nodes[i - 2])) {
+                // Caption below an uncaptioned table: move it above
+                nodes.swap(i - 1, i);
+                context.count(&self.name, "captions", 1);
+                context.count(&self.name, "moved_above", 1);
+                context.record(&self.name, format!("table {}: caption \"{}\" moved above the table", table_number, caption), position.as_ref());
+            }
+            i += 1;
+        }
+
+        nodes
+    }
+}
+
+impl NodeProcessor for TableCaptionProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        let children = match node {
+            Node::Root(root) => &root.children,
+            Node::Blockquote(quote) => &quote.children,
+            _ => return false,
+        };
+        // Glued captions share a paragraph with table lines, so look at every line
+        children.iter().any(|child| {
+            matches!(child, Node::Paragraph(_)) && inline_text(child).lines().any(is_caption_text)
+        })
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        match &mut node {
+            Node::Root(root) => {
+                root.children = self.place_captions(std::mem::take(&mut root.children), context);
+            }
+            Node::Blockquote(quote) => {
+                quote.children = self.place_captions(std::mem::take(&mut quote.children), context);
+            }
+            _ => {}
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
@@ -0,0 +1,149 @@
+use anyhow::Result;
+use markdown::mdast::{Code, List, ListItem, Node, Paragraph, Table, Text};
+use super::paragraph_lines::inline_text;
+use super::table_processor::pipe_paragraph_to_table;
+use super::traits::{NodeProcessor, ProcessContext};
+
+/// Linearized form tables are rewritten into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableLinearization {
+    /// One list item per row: `- 品名: りんご; 価格: 100`
+    KeyValue,
+    /// A `tsv` code block, header first
+    Tsv,
+    /// A paragraph with one sentence per row: `品名はりんご、価格は100。` / `Name is apple, price is 100.`
+    Sentence,
+}
+
+fn is_cjk(ch: char) -> bool {
+    matches!(ch as u32, 0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF)
+}
+
+/// Cell text with surrounding whitespace trimmed and inner runs collapsed
+fn cell_text(cell: &Node) -> String {
+    inline_text(cell).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn text_paragraph(value: String) -> Node {
+    Node::Paragraph(Paragraph {
+        children: vec![Node::Text(Text { value, position: None })],
+        position: None,
+    })
+}
+
+/// Table linearization processor
+/// Padded pipe tables are a poor fit for retrieval and LLM prompts: the header is far from
+/// the values and the padding costs tokens. Tables (including pipe tables kept as text) are
+/// rewritten as key-value lists, a compact TSV code block or one sentence per row. Add it
+/// last, after the processors that build or repair tables.
+pub struct TableLinearizeProcessor {
+    name: String,
+    style: TableLinearization,
+}
+
+impl Default for TableLinearizeProcessor {
+    fn default() -> Self {
+        Self::new(TableLinearization::KeyValue)
+    }
+}
+
+impl TableLinearizeProcessor {
+    pub fn new(style: TableLinearization) -> Self {
+        Self {
+            name: "TableLinearizeProcessor".to_string(),
+            style,
+        }
+    }
+
+    /// Header names and data rows; empty header cells become `Column N`
+    fn split_table(table: &Table) -> (Vec<String>, Vec<Vec<String>>) {
+        let mut rows = table.children.iter()
+            .map(|row| row.children().map(|cells| cells.iter().map(cell_text).collect()).unwrap_or_default());
+        let header: Vec<String> = rows.next().unwrap_or_default();
+        let header = header.into_iter()
+            .enumerate()
+            .map(|(index, name)| if name.is_empty() { format!("Column {}", index + 1) } else { name })
+            .collect();
+        (header, rows.collect())
+    }
+
+    fn key_value(header: &[String], rows: &[Vec<String>]) -> Node {
+        let children = rows.iter()
+            .map(|row| {
+                let pairs = header.iter().zip(row)
+                    .filter(|(_, value)| !value.is_empty())
+                    .map(|(key, value)| format!("{}: {}", key, value))
+                    .collect::<Vec<_>>();
+                Node::ListItem(ListItem {
+                    children: vec![text_paragraph(pairs.join("; "))],
+                    position: None,
+                    spread: false,
+                    checked: None,
+                })
+            })
+            .collect();
+        Node::List(List { children, position: None, ordered: false, start: None, spread: false })
+    }
+
+    fn tsv(header: &[String], rows: &[Vec<String>]) -> Node {
+        let value = std::iter::once(header)
+            .chain(rows.iter().map(Vec::as_slice))
+            .map(|row| row.iter().map(|cell| cell.replace('\t', " ")).collect::<Vec<_>>().join("\t"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Node::Code(Code { value, position: None, lang: Some("tsv".to_string()), meta: None })
+    }
+
+    fn sentences(header: &[String], rows: &[Vec<String>]) -> Node {
+        let japanese = header.iter().chain(rows.iter().flatten()).any(|cell| cell.chars().any(is_cjk));
+        let sentences = rows.iter()
+            .map(|row| {
+                let clauses = header.iter().zip(row)
+                    .filter(|(_, value)| !value.is_empty())
+                    .map(|(key, value)| if japanese { format!("{}は{}", key, value) } else { format!("{} is {}", key, value) })
+                    .collect::<Vec<_>>();
+                if japanese { format!("{}。", clauses.join("、")) } else { format!("{}.", clauses.join(", ")) }
+            })
+            .collect::<Vec<_>>();
+        // One flowing paragraph; Japanese sentences need no space after `。`
+        text_paragraph(sentences.join(if japanese { "" } else { " " }))
+    }
+}
+
+impl NodeProcessor for TableLinearizeProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        match node {
+            Node::Table(_) => true,
+            Node::Paragraph(paragraph) => pipe_paragraph_to_table(paragraph).is_some(),
+            _ => false,
+        }
+    }
+
+    fn process_node(&self, node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        let table = match &node {
+            Node::Table(table) => table.clone(),
+            Node::Paragraph(paragraph) => match pipe_paragraph_to_table(paragraph) {
+                Some(table) => table,
+                None => return Ok(Some(node)),
+            },
+            _ => return Ok(Some(node)),
+        };
+
+        let (header, rows) = Self::split_table(&table);
+        if rows.is_empty() {
+            return Ok(Some(node));
+        }
+        context.count(&self.name, "tables", 1);
+        context.count(&self.name, "rows", rows.len());
+
+        Ok(Some(match self.style {
+            TableLinearization::KeyValue => Self::key_value(&header, &rows),
+            TableLinearization::Tsv => Self::tsv(&header, &rows),
+            TableLinearization::Sentence => Self::sentences(&header, &rows),
+        }))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
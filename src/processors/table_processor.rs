@@ -1,21 +1,299 @@
 use anyhow::Result;
-use markdown::mdast::Node;
+use markdown::mdast::{AlignKind, Node, Table, TableCell, TableRow, Text};
+use markdown::mdast::Paragraph;
+use super::paragraph_lines::{any_line_matches, convert_line_runs, join_lines, split_lines, RunItem};
 use super::traits::{NodeProcessor, ProcessContext};
 
+/// A pipe-table line split into cells (inline nodes, so images inside cells survive)
+#[derive(Debug, Clone)]
+pub(crate) struct PipeRow {
+    pub cells: Vec<Vec<Node>>,
+    /// Alignments when this is a `|---|:--:|` delimiter row
+    pub delimiter: Option<Vec<AlignKind>>,
+}
+
+fn cell_text(cell: &[Node]) -> String {
+    cell.iter()
+        .map(|node| match node {
+            Node::Text(text) => text.value.as_str(),
+            _ => "\u{FFFC}",
+        })
+        .collect()
+}
+
+fn trim_cell(mut cell: Vec<Node>) -> Vec<Node> {
+    if let Some(Node::Text(text)) = cell.first_mut() {
+        text.value = text.value.trim_start().to_string();
+    }
+    if let Some(Node::Text(text)) = cell.last_mut() {
+        text.value = text.value.trim_end().to_string();
+    }
+    cell.retain(|node| !matches!(node, Node::Text(text) if text.value.is_empty()));
+    cell
+}
+
+/// Merge adjacent Text nodes so per-node whitespace cleaning sees whole cell text
+fn merge_text_nodes(nodes: Vec<Node>) -> Vec<Node> {
+    let mut merged: Vec<Node> = Vec::new();
+    for node in nodes {
+        match (merged.last_mut(), node) {
+            (Some(Node::Text(previous)), Node::Text(text)) => previous.value.push_str(&text.value),
+            (_, node) => merged.push(node),
+        }
+    }
+    merged
+}
+
+fn delimiter_alignment(cell: &str) -> Option<AlignKind> {
+    let cell = cell.trim();
+    let inner = cell.trim_start_matches(':').trim_end_matches(':');
+    if inner.is_empty() || !inner.chars().all(|ch| ch == '-') {
+        return None;
+    }
+    Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+        (true, true) => AlignKind::Center,
+        (true, false) => AlignKind::Left,
+        (false, true) => AlignKind::Right,
+        (false, false) => AlignKind::None,
+    })
+}
+
+/// Split a line into pipe-table cells; lines need a leading pipe or at least two pipes
+pub(crate) fn split_pipe_row(line: &[Node]) -> Option<(PipeRow, usize)> {
+    let text: String = line.iter()
+        .filter_map(|node| match node {
+            Node::Text(text) => Some(text.value.as_str()),
+            _ => None,
+        })
+        .collect();
+    let pipes = text.matches('|').count();
+    if pipes < 2 && !(pipes == 1 && text.trim_start().starts_with('|')) {
+        return None;
+    }
+
+    let mut cells: Vec<Vec<Node>> = vec![Vec::new()];
+    for node in line {
+        match node {
+            Node::Text(text) => {
+                let mut current = String::new();
+                let mut chars = text.value.chars().peekable();
+                while let Some(ch) = chars.next() {
+                    match ch {
+                        '\\' if chars.peek() == Some(&'|') => {
+                            current.push('|');
+                            chars.next();
+                        }
+                        '|' => {
+                            if !current.is_empty() {
+                                cells.last_mut().unwrap().push(Node::Text(Text { value: std::mem::take(&mut current), position: None }));
+                            }
+                            cells.push(Vec::new());
+                        }
+                        _ => current.push(ch),
+                    }
+                }
+                if !current.is_empty() {
+                    cells.last_mut().unwrap().push(Node::Text(Text { value: current, position: None }));
+                }
+            }
+            other => cells.last_mut().unwrap().push(other.clone()),
+        }
+    }
+
+    let mut cells: Vec<Vec<Node>> = cells.into_iter().map(trim_cell).collect();
+    // Leading and trailing pipes produce empty outer cells
+    if text.trim_start().starts_with('|') && cells.first().is_some_and(|cell| cell.is_empty()) {
+        cells.remove(0);
+    }
+    if text.trim_end().ends_with('|') && cells.last().is_some_and(|cell| cell.is_empty()) {
+        cells.pop();
+    }
+
+    let delimiter = cells.iter()
+        .map(|cell| delimiter_alignment(&cell_text(cell)))
+        .collect::<Option<Vec<_>>>()
+        .filter(|aligns| !aligns.is_empty());
+
+    Some((PipeRow { cells, delimiter }, 0))
+}
+
+/// Parse a paragraph holding a well-formed pipe table (header, delimiter row, rows)
+pub(crate) fn pipe_paragraph_to_table(paragraph: &Paragraph) -> Option<Table> {
+    let rows = split_lines(paragraph, 0)
+        .into_iter()
+        .map(|line| split_pipe_row(&line.inlines).map(|(row, _)| row))
+        .collect::<Option<Vec<_>>>()?;
+    let align = rows.get(1)?.delimiter.clone()?;
+    if rows[0].delimiter.is_some() {
+        return None;
+    }
+
+    let children = rows.into_iter()
+        .enumerate()
+        .filter(|(index, _)| *index != 1)
+        .map(|(_, row)| Node::TableRow(TableRow {
+            children: row.cells.into_iter()
+                .map(|cell| Node::TableCell(TableCell { children: cell, position: None }))
+                .collect(),
+            position: None,
+        }))
+        .collect();
+
+    Some(Table { children, position: paragraph.position.clone(), align })
+}
+
 /// Table preservation processor
 /// Preserves table formatting from PDF-extracted content
 /// Tables often get mangled during PDF-to-markdown conversion, so we preserve them as-is
+/// With repair enabled, pipe tables that are not valid GFM (missing delimiter row,
+/// ragged rows) are rebuilt as `Node::Table`s; well-formed ones are still left untouched.
 pub struct TableProcessor {
     name: String,
+    repair: bool,
+}
+
+impl Default for TableProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TableProcessor {
     pub fn new() -> Self {
         Self {
             name: "TableProcessor".to_string(),
+            repair: false,
+        }
+    }
+
+    /// Repair malformed pipe tables: synthesize the delimiter row, pad short rows
+    /// and merge overflow cells, reporting each repair
+    pub fn with_repair(mut self, repair: bool) -> Self {
+        self.repair = repair;
+        self
+    }
+
+    /// Header, delimiter row, then rows of the header's width; later rows of dashes are data
+    fn is_well_formed(rows: &[RunItem<PipeRow>]) -> bool {
+        let columns = rows[0].marker.cells.len();
+        rows.len() >= 2
+            && rows[0].marker.delimiter.is_none()
+            && rows[1].marker.delimiter.as_ref().is_some_and(|aligns| aligns.len() == columns)
+            && rows.iter().skip(2).all(|row| row.marker.cells.len() == columns)
+    }
+
+    /// Column count: the most common row width, preferring the header's on ties
+    fn infer_columns(rows: &[PipeRow]) -> usize {
+        let header = rows[0].cells.len();
+        let mut best = (header, rows.iter().filter(|row| row.cells.len() == header).count());
+        for row in rows {
+            let width = row.cells.len();
+            let frequency = rows.iter().filter(|other| other.cells.len() == width).count();
+            if frequency > best.1 {
+                best = (width, frequency);
+            }
         }
+        best.0
     }
 
+    /// Build a table from a run of pipe rows, or `None` when there is nothing to repair
+    fn repair_table(&self, items: Vec<RunItem<PipeRow>>, context: &ProcessContext) -> Option<Node> {
+        // Rows of bare pipes (`|`) or only dashes have nothing to build columns from
+        let data_rows = items.iter().filter(|item| item.marker.delimiter.is_none() && !item.marker.cells.is_empty()).count();
+        if data_rows == 0 || Self::is_well_formed(&items) {
+            return None;
+        }
+
+        // Only the row right after the header is a delimiter row; rows of dashes elsewhere are data
+        let mut align = None;
+        let mut rows = Vec::new();
+        for (index, item) in items.into_iter().enumerate() {
+            match item.marker.delimiter {
+                Some(aligns) if index == 1 => align = Some(aligns),
+                _ if item.marker.cells.is_empty() => {
+                    context.record(&self.name, format!("removed empty row {}", index + 1), None);
+                }
+                _ => rows.push(item.marker),
+            }
+        }
+        if align.is_none() {
+            context.count(&self.name, "delimiter_rows_synthesized", 1);
+            context.record(&self.name, "synthesized missing delimiter row; first row used as header", None);
+        }
+
+        let columns = Self::infer_columns(&rows);
+        let children = rows.into_iter()
+            .enumerate()
+            .map(|(index, row)| {
+                let mut cells = row.cells;
+                if cells.len() < columns {
+                    context.count(&self.name, "rows_padded", 1);
+                    context.record(&self.name, format!("padded row {} from {} to {} cells", index + 1, cells.len(), columns), None);
+                    cells.resize(columns, Vec::new());
+                } else if cells.len() > columns {
+                    context.count(&self.name, "rows_merged", 1);
+                    context.record(&self.name, format!("merged {} overflow cells into the last cell of row {}", cells.len() - columns, index + 1), None);
+                    let overflow = cells.split_off(columns - 1);
+                    let merged = overflow.into_iter()
+                        .reduce(|mut merged, cell| {
+                            merged.push(Node::Text(Text { value: " ".to_string(), position: None }));
+                            merged.extend(cell);
+                            merged
+                        })
+                        .unwrap_or_default();
+                    cells.push(merge_text_nodes(merged));
+                }
+                Node::TableRow(TableRow {
+                    children: cells.into_iter()
+                        .map(|cell| Node::TableCell(TableCell { children: cell, position: None }))
+                        .collect(),
+                    position: None,
+                })
+            })
+            .collect();
+
+        let mut align = align.unwrap_or_default();
+        align.resize(columns, AlignKind::None);
+        context.count(&self.name, "tables_repaired", 1);
+        Some(Node::Table(Table { children, position: None, align }))
+    }
+
+    /// Repair each paragraph's pipe-row runs; paragraphs with nothing to repair are kept
+    /// as they are, positions included
+    fn repair_children(&self, children: Vec<Node>, context: &ProcessContext) -> Vec<Node> {
+        let mut result = Vec::with_capacity(children.len());
+        for child in children {
+            let Node::Paragraph(paragraph) = &child else {
+                result.push(child);
+                continue;
+            };
+            let mut repaired = false;
+            let converted = convert_line_runs(
+                vec![Node::Paragraph(paragraph.clone())],
+                split_pipe_row,
+                |_, _| true,
+                false,
+                false,
+                2,
+                |rows| {
+                    let lines = rows.iter().flat_map(|item| item.lines.clone()).collect();
+                    match self.repair_table(rows, context) {
+                        Some(table) => {
+                            repaired = true;
+                            table
+                        }
+                        None => join_lines(lines),
+                    }
+                },
+            );
+            if repaired {
+                result.extend(converted);
+            } else {
+                result.push(child);
+            }
+        }
+        result
+    }
 }
 
 impl NodeProcessor for TableProcessor {
@@ -28,13 +306,24 @@ impl NodeProcessor for TableProcessor {
                 text.value.matches('|').count() >= 2 &&
                 !text.value.starts_with("![")
             }
+            Node::Root(root) if self.repair => any_line_matches(&root.children, split_pipe_row),
+            Node::Blockquote(quote) if self.repair => any_line_matches(&quote.children, split_pipe_row),
             _ => false,
         }
     }
 
-    fn process_node(&self, node: Node, _context: &ProcessContext) -> Result<Option<Node>> {
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
         // Tables are preserved as-is - no modification needed
         // This includes both proper table nodes and paragraph-based tables
+        match &mut node {
+            Node::Root(root) => {
+                root.children = self.repair_children(std::mem::take(&mut root.children), context);
+            }
+            Node::Blockquote(quote) => {
+                quote.children = self.repair_children(std::mem::take(&mut quote.children), context);
+            }
+            _ => {}
+        }
         Ok(Some(node))
     }
 
@@ -0,0 +1,210 @@
+use std::sync::OnceLock;
+use anyhow::Result;
+use markdown::mdast::{Node, Table, TableCell, TableRow};
+use regex::Regex;
+use super::paragraph_lines::{inline_text, split_lines};
+use super::table_processor::{pipe_paragraph_to_table, split_pipe_row};
+use super::traits::{NodeProcessor, ProcessContext};
+
+fn page_artifact_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"(?ix)^\s*(?:
+            [-–—]\s*\d+\s*[-–—]                                  # - 12 -
+            | \d+                                                 # 12
+            | \d+\s*/\s*\d+                                       # 3 / 10
+            | (?:page|p\.)\s*\d+(?:\s*(?:of|/)\s*\d+)?            # Page 3 of 10
+            | \d+\s*(?:ページ|頁)                                  # 3ページ
+            | (?:continued|続く|次ページへ続く|\(続き\)|（続き）)      # continuation notes
+        )\s*$").expect("valid page artifact pattern")
+    })
+}
+
+/// Cell texts of a row with all whitespace removed, for header comparison
+fn row_signature(row: &Node) -> Vec<String> {
+    row.children()
+        .map(|cells| {
+            cells.iter()
+                .map(|cell| inline_text(cell).chars().filter(|ch| !ch.is_whitespace()).collect())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Table split across pages stitching processor
+/// Long tables are cut at page boundaries, leaving two tables, sometimes with a repeated
+/// header and a page footer (`- 12 -`, `Page 3`) or thematic break in between. Consecutive
+/// tables with the same column count, separated by nothing but page artifacts, are merged into
+/// one when the second repeats the first one's header or is header-less pipe rows; the repeated
+/// header and the page artifacts are dropped. A separator alone is not enough: unrelated tables
+/// divided by `---` or a number are kept apart. This works on the document, so add it after the processors that build
+/// tables (AlignedTableProcessor, TableProcessor repair).
+pub struct TableStitchProcessor {
+    name: String,
+    require_matching_header: bool,
+}
+
+impl Default for TableStitchProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TableStitchProcessor {
+    pub fn new() -> Self {
+        Self {
+            name: "TableStitchProcessor".to_string(),
+            require_matching_header: false,
+        }
+    }
+
+    /// Only merge tables that repeat the first one's header row, leaving header-less rows alone
+    pub fn with_require_matching_header(mut self, require: bool) -> Self {
+        self.require_matching_header = require;
+        self
+    }
+
+    fn as_table(node: &Node) -> Option<Table> {
+        match node {
+            Node::Table(table) => Some(table.clone()),
+            Node::Paragraph(paragraph) => pipe_paragraph_to_table(paragraph),
+            _ => None,
+        }
+    }
+
+    /// Rows of a paragraph made only of pipe rows without a delimiter row: the rest of a
+    /// table whose header stayed on the previous page
+    fn headerless_rows(node: &Node) -> Option<Vec<Node>> {
+        let Node::Paragraph(paragraph) = node else {
+            return None;
+        };
+        split_lines(paragraph, 0)
+            .iter()
+            .map(|line| match split_pipe_row(&line.inlines) {
+                Some((row, _)) if row.delimiter.is_none() && !row.cells.is_empty() => Some(Node::TableRow(TableRow {
+                    children: row.cells.into_iter()
+                        .map(|cell| Node::TableCell(TableCell { children: cell, position: None }))
+                        .collect(),
+                    position: None,
+                })),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn is_page_artifact(node: &Node) -> bool {
+        match node {
+            Node::ThematicBreak(_) => true,
+            Node::Html(html) => {
+                let value = html.value.to_lowercase();
+                value.starts_with("<!--") && value.contains("page")
+            }
+            Node::Paragraph(_) => page_artifact_pattern().is_match(&inline_text(node)),
+            // `- 12 -` parses as a bullet list holding `12 -`
+            Node::List(list) if !list.ordered && list.children.len() == 1 => {
+                page_artifact_pattern().is_match(&format!("- {}", inline_text(node)))
+            }
+            _ => false,
+        }
+    }
+
+    fn columns(table: &Table) -> usize {
+        table.children.first()
+            .and_then(|row| row.children())
+            .map(|cells| cells.len())
+            .unwrap_or(0)
+    }
+
+    /// Merge `next` into `previous` if it repeats the header of the same-width `previous`
+    fn merge(&self, previous: &mut Table, next: Table) -> bool {
+        if Self::columns(previous) != Self::columns(&next) {
+            return false;
+        }
+
+        let repeated_header = match (previous.children.first(), next.children.first()) {
+            (Some(header), Some(next_header)) => row_signature(header) == row_signature(next_header),
+            _ => false,
+        };
+        if !repeated_header {
+            return false;
+        }
+
+        previous.children.extend(next.children.into_iter().skip(1));
+        true
+    }
+
+    /// Append header-less rows to `previous` if their width matches
+    fn append_rows(&self, previous: &mut Table, rows: Vec<Node>) -> bool {
+        let columns = Self::columns(previous);
+        if self.require_matching_header || rows.iter().any(|row| row.children().map_or(0, |cells| cells.len()) != columns) {
+            return false;
+        }
+        previous.children.extend(rows);
+        true
+    }
+
+    fn stitch_children(&self, children: Vec<Node>, context: &ProcessContext) -> Vec<Node> {
+        let mut result: Vec<Node> = Vec::new();
+
+        for child in children {
+            let table = Self::as_table(&child);
+            let rows = if table.is_none() { Self::headerless_rows(&child) } else { None };
+            if table.is_none() && rows.is_none() {
+                result.push(child);
+                continue;
+            }
+
+            // The previous table, if only page artifacts separate it from this one
+            let previous_index = result.iter()
+                .rposition(|node| !Self::is_page_artifact(node))
+                .filter(|&index| Self::as_table(&result[index]).is_some());
+
+            if let Some(index) = previous_index {
+                let mut previous = Self::as_table(&result[index]).expect("checked above");
+                let merged = match (table, rows) {
+                    (Some(table), _) => self.merge(&mut previous, table),
+                    (None, Some(rows)) => self.append_rows(&mut previous, rows),
+                    (None, None) => false,
+                };
+                if merged {
+                    let dropped = result.len() - index - 1;
+                    for artifact in result.drain(index + 1..) {
+                        context.record(&self.name, format!("dropped page artifact between tables: {}", inline_text(&artifact).trim()), None);
+                    }
+                    context.count(&self.name, "tables_stitched", 1);
+                    context.count(&self.name, "page_artifacts_dropped", dropped);
+                    result[index] = Node::Table(previous);
+                    continue;
+                }
+            }
+
+            result.push(child);
+        }
+
+        result
+    }
+}
+
+impl NodeProcessor for TableStitchProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        match node {
+            Node::Root(root) => {
+                root.children.iter()
+                    .filter(|child| Self::as_table(child).is_some() || Self::headerless_rows(child).is_some())
+                    .count() >= 2
+            }
+            _ => false,
+        }
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        if let Node::Root(root) = &mut node {
+            root.children = self.stitch_children(std::mem::take(&mut root.children), context);
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
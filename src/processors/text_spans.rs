@@ -0,0 +1,51 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// A slice of a Text node value, marked as protected when it must not be rewritten
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextSpan<'a> {
+    pub text: &'a str,
+    pub protected: bool,
+}
+
+fn protected_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"(?x)
+            \$\$[^$]+\$\$                          # display math
+            | \$[^$\s](?:[^$\n]*[^$\s])?\$         # inline math
+            | (?:https?|ftp)://[A-Za-z0-9\-._~:/?\#\[\]@!$&'*+,;=%]+  # bare URLs
+            | www\.[A-Za-z0-9\-._~:/?\#\[\]@!$&'*+,;=%]+
+            | [A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)+     # e-mail addresses
+        ").expect("valid protected span pattern")
+    })
+}
+
+/// Split text into spans, marking URLs, e-mail addresses and `$...$` math as protected
+/// Text nodes never contain code (inline code is a separate node), so these are the
+/// remaining places where character-level rewrites would corrupt content
+pub fn split_protected(text: &str) -> Vec<TextSpan<'_>> {
+    let mut spans = Vec::new();
+    let mut last = 0;
+
+    for found in protected_pattern().find_iter(text) {
+        if found.start() > last {
+            spans.push(TextSpan { text: &text[last..found.start()], protected: false });
+        }
+        spans.push(TextSpan { text: found.as_str(), protected: true });
+        last = found.end();
+    }
+    if last < text.len() {
+        spans.push(TextSpan { text: &text[last..], protected: false });
+    }
+
+    spans
+}
+
+/// Apply `rewrite` to the unprotected parts of `text`, copying protected spans verbatim
+pub fn map_unprotected(text: &str, mut rewrite: impl FnMut(&str) -> String) -> String {
+    split_protected(text)
+        .into_iter()
+        .map(|span| if span.protected { span.text.to_string() } else { rewrite(span.text) })
+        .collect()
+}
\ No newline at end of file
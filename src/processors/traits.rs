@@ -1,9 +1,31 @@
+use std::cell::RefCell;
 use anyhow::Result;
 use markdown::mdast::Node;
+use markdown::unist::Position;
+use crate::report::{ProcessReport, ReportEntry};
 
 #[derive(Debug, Default)]
 pub struct ProcessContext {
     pub file_path: Option<String>,
+    /// Markdown being cleaned, set by `MarkdownCleaner` so positions can be resolved
+    pub source: Option<String>,
+    pub report: RefCell<ProcessReport>,
+}
+
+impl ProcessContext {
+    /// Add `amount` to a counter in the processing report
+    pub fn count(&self, processor: &str, counter: &str, amount: usize) {
+        self.report.borrow_mut().increment(processor, counter, amount);
+    }
+
+    /// Record an entry in the processing report
+    pub fn record(&self, processor: &str, message: impl Into<String>, position: Option<&Position>) {
+        self.report.borrow_mut().add_entry(ReportEntry {
+            processor: processor.to_string(),
+            message: message.into(),
+            position: position.cloned(),
+        });
+    }
 }
 
 /// AI-specialized markdown cleaner trait
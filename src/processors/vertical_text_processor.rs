@@ -0,0 +1,197 @@
+use anyhow::Result;
+use markdown::mdast::{Node, Paragraph, Text};
+use markdown::unist::Position;
+use super::traits::{NodeProcessor, ProcessContext};
+use super::whitespace_processor::CharClass;
+
+/// Vertical presentation forms and their horizontal counterparts
+const VERTICAL_FORMS: &[(char, char)] = &[
+    ('︐', '，'), ('︑', '、'), ('︒', '。'), ('︓', '：'), ('︔', '；'), ('︕', '！'), ('︖', '？'),
+    ('︗', '〖'), ('︘', '〗'), ('︙', '…'), ('︰', '‥'), ('︱', 'ー'), ('︲', '–'),
+    ('︵', '（'), ('︶', '）'), ('︷', '｛'), ('︸', '｝'), ('︹', '〔'), ('︺', '〕'), ('︻', '【'), ('︼', '】'),
+    ('︽', '《'), ('︾', '》'), ('︿', '〈'), ('﹀', '〉'), ('﹁', '「'), ('﹂', '」'), ('﹃', '『'), ('﹄', '』'),
+];
+
+const SENTENCE_ENDS: &str = "。！？";
+const CLOSING_BRACKETS: &str = "」』）〕】〉》";
+
+fn is_vertical_char(ch: char) -> bool {
+    CharClass::Han.contains(ch)
+        || CharClass::Kana.contains(ch)
+        || CharClass::CjkPunctuation.contains(ch)
+        || CharClass::FullwidthForms.contains(ch)
+        || matches!(ch as u32, 0xFE10..=0xFE19 | 0xFE30..=0xFE4F)
+        || matches!(ch, '…' | '‥' | '―')
+        || ch.is_ascii_digit()
+}
+
+fn horizontal_form(ch: char) -> char {
+    VERTICAL_FORMS.iter().find(|(vertical, _)| *vertical == ch).map(|(_, horizontal)| *horizontal).unwrap_or(ch)
+}
+
+/// Join column fragments into flowing text, one sentence per line
+fn reassemble(lines: &[&str]) -> String {
+    let text: Vec<char> = lines.iter().flat_map(|line| line.trim().chars()).map(horizontal_form).collect();
+    let mut result = String::with_capacity(text.len() * 3);
+    for (index, &ch) in text.iter().enumerate() {
+        result.push(ch);
+        let next = text.get(index + 1).copied();
+        let sentence_end = SENTENCE_ENDS.contains(ch) || (CLOSING_BRACKETS.contains(ch) && index > 0 && SENTENCE_ENDS.contains(text[index - 1]));
+        let continues = next.is_some_and(|next| SENTENCE_ENDS.contains(next) || CLOSING_BRACKETS.contains(next));
+        if sentence_end && !continues && next.is_some() {
+            result.push('\n');
+        }
+    }
+    result
+}
+
+/// Vertical-writing (tategaki) reassembly processor
+/// Vertically typeset Japanese extracts one or two characters per line, sometimes one per
+/// block. Runs of at least `min_lines` such short CJK lines, within a paragraph or across
+/// consecutive paragraphs, are joined back into flowing text with a line break after each
+/// sentence end (`。！？`), and vertical presentation forms (`︒`, `﹁`) become horizontal.
+pub struct VerticalTextProcessor {
+    name: String,
+    min_lines: usize,
+    max_line_chars: usize,
+}
+
+impl Default for VerticalTextProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VerticalTextProcessor {
+    pub fn new() -> Self {
+        Self {
+            name: "VerticalTextProcessor".to_string(),
+            min_lines: 6,
+            max_line_chars: 2,
+        }
+    }
+
+    /// Shortest run of short lines treated as vertical text (default 6)
+    pub fn with_min_lines(mut self, min_lines: usize) -> Self {
+        self.min_lines = min_lines.max(2);
+        self
+    }
+
+    /// Longest line, in characters, that can be a vertical column fragment (default 2)
+    pub fn with_max_line_chars(mut self, max_line_chars: usize) -> Self {
+        self.max_line_chars = max_line_chars.max(1);
+        self
+    }
+
+    fn is_short_line(&self, line: &str) -> bool {
+        let line = line.trim();
+        !line.is_empty() && line.chars().count() <= self.max_line_chars && line.chars().all(is_vertical_char)
+    }
+
+    /// Paragraph made only of text lines that are all short
+    fn is_fragment_paragraph(&self, node: &Node) -> bool {
+        match node {
+            Node::Paragraph(paragraph) => {
+                !paragraph.children.is_empty()
+                    && paragraph.children.iter().all(|child| {
+                        matches!(child, Node::Text(text) if text.value.lines().all(|line| self.is_short_line(line)))
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    /// Merge consecutive paragraphs of short lines into one paragraph, one fragment per line
+    fn merge_fragment_paragraphs(&self, children: &mut Vec<Node>) {
+        let mut merged: Vec<Node> = Vec::with_capacity(children.len());
+        let mut group: Vec<Node> = Vec::new();
+        for child in children.drain(..) {
+            if self.is_fragment_paragraph(&child) {
+                group.push(child);
+            } else {
+                self.flush_fragments(&mut group, &mut merged);
+                merged.push(child);
+            }
+        }
+        self.flush_fragments(&mut group, &mut merged);
+        *children = merged;
+    }
+
+    fn flush_fragments(&self, group: &mut Vec<Node>, merged: &mut Vec<Node>) {
+        let lines: Vec<String> = group.iter()
+            .flat_map(|paragraph| paragraph.children().into_iter().flatten())
+            .filter_map(|text| match text {
+                Node::Text(text) => Some(text.value.clone()),
+                _ => None,
+            })
+            .collect();
+        if group.len() < 2 || lines.iter().map(|text| text.lines().count()).sum::<usize>() < self.min_lines {
+            merged.append(group);
+            return;
+        }
+
+        let position = match (group.first().and_then(Node::position), group.last().and_then(Node::position)) {
+            (Some(first), Some(last)) => Some(Position { start: first.start.clone(), end: last.end.clone() }),
+            _ => None,
+        };
+        merged.push(Node::Paragraph(Paragraph {
+            children: vec![Node::Text(Text { value: lines.join("\n"), position: position.clone() })],
+            position,
+        }));
+        group.clear();
+    }
+
+    fn reassemble_text(&self, text: &mut Text, context: &ProcessContext) {
+        let lines: Vec<&str> = text.value.split('\n').collect();
+        let mut result: Vec<String> = Vec::with_capacity(lines.len());
+        let mut index = 0;
+        while index < lines.len() {
+            let run = lines[index..].iter().take_while(|line| self.is_short_line(line)).count();
+            if run >= self.min_lines {
+                result.push(reassemble(&lines[index..index + run]));
+                context.count(&self.name, "runs", 1);
+                context.count(&self.name, "lines", run);
+                context.record(&self.name, format!("reassembled {} vertical lines", run), text.position.as_ref());
+                index += run;
+            } else {
+                result.extend(lines[index..index + run.max(1)].iter().map(|line| line.to_string()));
+                index += run.max(1);
+            }
+        }
+        text.value = result.join("\n");
+    }
+
+    fn reassemble_blocks(&self, children: &mut Vec<Node>, context: &ProcessContext) {
+        self.merge_fragment_paragraphs(children);
+        for child in children.iter_mut() {
+            match child {
+                Node::Paragraph(paragraph) => {
+                    for inline in paragraph.children.iter_mut() {
+                        if let Node::Text(text) = inline {
+                            self.reassemble_text(text, context);
+                        }
+                    }
+                }
+                Node::Blockquote(quote) => self.reassemble_blocks(&mut quote.children, context),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl NodeProcessor for VerticalTextProcessor {
+    fn should_process(&self, node: &Node) -> bool {
+        matches!(node, Node::Root(_))
+    }
+
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
+        if let Node::Root(root) = &mut node {
+            self.reassemble_blocks(&mut root.children, context);
+        }
+        Ok(Some(node))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
\ No newline at end of file
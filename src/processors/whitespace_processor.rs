@@ -1,21 +1,174 @@
+use std::ops::RangeInclusive;
 use anyhow::Result;
 use markdown::mdast::Node;
 use super::traits::{NodeProcessor, ProcessContext};
 
+/// Character classes between which `WhitespaceProcessor` removes whitespace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharClass {
+    /// CJK ideographs: unified (with Extensions A-I), compatibility ideographs and radicals
+    Han,
+    /// Hiragana and katakana, including half-width and supplementary kana
+    Kana,
+    /// Hangul syllables and jamo
+    Hangul,
+    /// Bopomofo (zhuyin)
+    Bopomofo,
+    /// CJK symbols and punctuation, enclosed and compatibility forms (`、。「」㈱㎝`)
+    CjkPunctuation,
+    /// Full-width ASCII and half-width forms (U+FF00..U+FFEF)
+    FullwidthForms,
+    /// ASCII digits, joined to adjacent CJK text (`表 1` → `表1`); whitespace between two digits
+    /// (`1 000`) is always kept
+    Digits,
+}
+
+impl CharClass {
+    pub fn contains(&self, ch: char) -> bool {
+        match self {
+            CharClass::Han => matches!(ch as u32,
+                0x4E00..=0x9FFF | 0x3400..=0x4DBF | 0x20000..=0x2A6DF | 0x2A700..=0x2EBEF | 0x30000..=0x323AF |
+                0xF900..=0xFAFF | 0x2F800..=0x2FA1F | 0x2E80..=0x2FDF),
+            CharClass::Kana => matches!(ch as u32,
+                0x3040..=0x309F | 0x30A0..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F | 0x1B000..=0x1B16F),
+            CharClass::Hangul => matches!(ch as u32,
+                0xAC00..=0xD7AF | 0x1100..=0x11FF | 0x3130..=0x318F | 0xA960..=0xA97F | 0xD7B0..=0xD7FF | 0xFFA0..=0xFFDC),
+            CharClass::Bopomofo => matches!(ch as u32, 0x3100..=0x312F | 0x31A0..=0x31BF),
+            CharClass::CjkPunctuation => matches!(ch as u32, 0x3000..=0x303F | 0x3200..=0x33FF | 0xFE30..=0xFE4F),
+            CharClass::FullwidthForms => matches!(ch as u32, 0xFF00..=0xFFEF),
+            CharClass::Digits => ch.is_ascii_digit(),
+        }
+    }
+}
+
+/// Default character classes for a language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LanguagePreset {
+    /// Han, kana, CJK punctuation, full-width forms and digits
+    Japanese,
+    /// Han, bopomofo, CJK punctuation, full-width forms and digits
+    Chinese,
+    /// Han, CJK punctuation and full-width forms; Hangul is left out because Korean
+    /// separates words with spaces
+    Korean,
+}
+
+impl LanguagePreset {
+    pub fn classes(&self) -> &'static [CharClass] {
+        match self {
+            LanguagePreset::Japanese => &[CharClass::Han, CharClass::Kana, CharClass::CjkPunctuation, CharClass::FullwidthForms, CharClass::Digits],
+            LanguagePreset::Chinese => &[CharClass::Han, CharClass::Bopomofo, CharClass::CjkPunctuation, CharClass::FullwidthForms, CharClass::Digits],
+            LanguagePreset::Korean => &[CharClass::Han, CharClass::CjkPunctuation, CharClass::FullwidthForms],
+        }
+    }
+}
+
 /// AI-specialized whitespace processor
 /// Cleans excessive whitespace from PDF-extracted markdown content
-/// while preserving structured elements like images and tables
+/// while preserving structured elements like images and tables.
+/// Whitespace is removed only between characters of the configured classes (a language
+/// preset, adjustable per class) or custom ranges; elsewhere runs collapse to one space.
 pub struct WhitespaceProcessor {
     name: String,
+    classes: Vec<CharClass>,
+    ranges: Vec<RangeInclusive<char>>,
+    keep_indent: bool,
+    inline_edges: bool,
+}
+
+impl Default for WhitespaceProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl WhitespaceProcessor {
+    /// Create a processor with the Japanese preset
     pub fn new() -> Self {
+        Self::with_preset(LanguagePreset::Japanese)
+    }
+
+    pub fn with_preset(preset: LanguagePreset) -> Self {
         Self {
             name: "WhitespaceProcessor".to_string(),
+            classes: preset.classes().to_vec(),
+            ranges: Vec::new(),
+            keep_indent: false,
+            inline_edges: false,
         }
     }
 
+    /// Enable or disable a character class
+    pub fn with_class(mut self, class: CharClass, enabled: bool) -> Self {
+        self.classes.retain(|existing| *existing != class);
+        if enabled {
+            self.classes.push(class);
+        }
+        self
+    }
+
+    /// Also treat the characters of `range` as full-width
+    pub fn with_range(mut self, range: RangeInclusive<char>) -> Self {
+        self.ranges.push(range);
+        self
+    }
+
+    /// Keep one ideographic space (U+3000) of paragraph and line indentation, and the line
+    /// break before an indented line (default false: indentation is removed)
+    pub fn with_keep_indent(mut self, keep_indent: bool) -> Self {
+        self.keep_indent = keep_indent;
+        self
+    }
+
+    /// Keep the edge spaces that separate a Text node from inline siblings (code, images)
+    /// unless they sit on the full-width side, as `CjkSpacingProcessor` needs (default false:
+    /// Text nodes are trimmed)
+    pub fn with_inline_edges(mut self, inline_edges: bool) -> Self {
+        self.inline_edges = inline_edges;
+        self
+    }
+
+    pub fn classes(&self) -> &[CharClass] {
+        &self.classes
+    }
+
+    /// Handle ideographic-space indentation at the start of the text and of each line, then
+    /// clean the rest; indented lines start a new line when the indentation is kept
+    fn clean_text(&self, text: &str, context: &ProcessContext) -> String {
+        let mut segments: Vec<String> = vec![String::new()];
+        let mut indented = Vec::new();
+        for (index, line) in text.split('\n').enumerate() {
+            let content = line.trim_start();
+            let indent = &line[..line.len() - content.len()];
+            let is_indent = indent.contains('\u{3000}') && !content.is_empty();
+            if is_indent {
+                context.count(&self.name, "indents", 1);
+            }
+
+            let segment = segments.last_mut().expect("at least one segment");
+            if is_indent && self.keep_indent && !(index == 0 && segment.is_empty()) {
+                segments.push(content.to_string());
+                indented.push(true);
+                continue;
+            }
+            if index == 0 {
+                indented.push(is_indent && self.keep_indent);
+            } else {
+                segment.push('\n');
+            }
+            segment.push_str(if is_indent { content } else { line });
+        }
+
+        segments.iter()
+            .zip(indented)
+            .map(|(segment, indented)| {
+                let cleaned = self.clean_whitespace(segment);
+                if indented { format!("\u{3000}{}", cleaned) } else { cleaned }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     /// Clean whitespace from text content
     /// Only processes full-width character regions, preserves spaces in half-width alphabet regions
     fn clean_whitespace(&self, text: &str) -> String {
@@ -32,8 +185,12 @@ impl WhitespaceProcessor {
                     .map(|c| self.is_fullwidth_char(*c))
                     .unwrap_or(false);
 
+                // Digit groups (`1 000`) are never joined
+                let between_digits = result.chars().last().is_some_and(|c| c.is_ascii_digit())
+                    && chars.peek().is_some_and(|c| c.is_ascii_digit());
+
                 // Only remove whitespace if both adjacent characters are full-width
-                if prev_is_fullwidth && next_is_fullwidth {
+                if prev_is_fullwidth && next_is_fullwidth && !between_digits {
                     // Skip excessive whitespace between full-width characters
                     continue;
                 } else {
@@ -47,26 +204,23 @@ impl WhitespaceProcessor {
             }
         }
 
-        result.trim().to_string()
+        if !self.inline_edges {
+            return result.trim().to_string();
+        }
+        // Edge spaces separate this node from its inline siblings (code, images); keep
+        // them unless they sit on the full-width side
+        if result.starts_with(' ') && result.chars().nth(1).is_some_and(|c| self.is_fullwidth_char(c)) {
+            result.remove(0);
+        }
+        if result.ends_with(' ') && result.chars().rev().nth(1).is_some_and(|c| self.is_fullwidth_char(c)) {
+            result.pop();
+        }
+        result
     }
 
-    /// Check if a character is full-width (CJK characters, full-width punctuation, etc.)
+    /// Check if a character belongs to a configured class or range
     fn is_fullwidth_char(&self, ch: char) -> bool {
-        match ch as u32 {
-            // CJK Unified Ideographs
-            0x4E00..=0x9FFF |
-            // Hiragana
-            0x3040..=0x309F |
-            // Katakana
-            0x30A0..=0x30FF |
-            // Full-width forms
-            0xFF00..=0xFFEF |
-            // CJK Symbols and Punctuation
-            0x3000..=0x303F |
-            // Also consider digits as "cleanable" when next to CJK
-            0x0030..=0x0039 => true,
-            _ => false,
-        }
+        self.classes.iter().any(|class| class.contains(ch)) || self.ranges.iter().any(|range| range.contains(&ch))
     }
 
 }
@@ -82,10 +236,10 @@ impl NodeProcessor for WhitespaceProcessor {
         }
     }
 
-    fn process_node(&self, mut node: Node, _context: &ProcessContext) -> Result<Option<Node>> {
+    fn process_node(&self, mut node: Node, context: &ProcessContext) -> Result<Option<Node>> {
         match &mut node {
             Node::Text(text) => {
-                text.value = self.clean_whitespace(&text.value);
+                text.value = self.clean_text(&text.value, context);
                 Ok(Some(node))
             }
             _ => Ok(Some(node)),
@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use markdown::unist::Position;
+
+/// A single noteworthy event recorded by a processor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportEntry {
+    pub processor: String,
+    pub message: String,
+    pub position: Option<Position>,
+}
+
+/// Processing report collected while cleaning a document
+/// Holds per-processor counters and individual entries (repairs, warnings, findings)
+#[derive(Debug, Default, Clone)]
+pub struct ProcessReport {
+    counters: BTreeMap<(String, String), usize>,
+    entries: Vec<ReportEntry>,
+}
+
+impl ProcessReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `amount` to the counter `counter` of `processor`
+    pub fn increment(&mut self, processor: &str, counter: &str, amount: usize) {
+        if amount == 0 {
+            return;
+        }
+        *self.counters
+            .entry((processor.to_string(), counter.to_string()))
+            .or_insert(0) += amount;
+    }
+
+    /// Get the current value of a counter (0 if never incremented)
+    pub fn count(&self, processor: &str, counter: &str) -> usize {
+        self.counters
+            .get(&(processor.to_string(), counter.to_string()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Iterate over all counters as (processor, counter, value)
+    pub fn counters(&self) -> impl Iterator<Item = (&str, &str, usize)> {
+        self.counters
+            .iter()
+            .map(|((processor, counter), value)| (processor.as_str(), counter.as_str(), *value))
+    }
+
+    pub fn add_entry(&mut self, entry: ReportEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn entries(&self) -> &[ReportEntry] {
+        &self.entries
+    }
+
+    /// Entries recorded by a specific processor
+    pub fn entries_for<'a>(&'a self, processor: &'a str) -> impl Iterator<Item = &'a ReportEntry> {
+        self.entries.iter().filter(move |entry| entry.processor == processor)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counters.is_empty() && self.entries.is_empty()
+    }
+}
+
+impl fmt::Display for ProcessReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (processor, counter, value) in self.counters() {
+            writeln!(f, "{}: {} = {}", processor, counter, value)?;
+        }
+        for entry in &self.entries {
+            match &entry.position {
+                Some(position) => writeln!(
+                    f,
+                    "{} [{}:{}]: {}",
+                    entry.processor, position.start.line, position.start.column, entry.message
+                )?,
+                None => writeln!(f, "{}: {}", entry.processor, entry.message)?,
+            }
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
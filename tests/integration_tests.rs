@@ -1,5 +1,13 @@
 use std::sync::Arc;
-use mdclean::{MarkdownCleaner, WhitespaceProcessor, ImageProcessor, TableProcessor};
+use mdclean::{MarkdownCleaner, WhitespaceProcessor, CharClass, LanguagePreset, ImageProcessor, TableProcessor, LigatureProcessor,
+             NormalizationProcessor, NormalizationPolicy,
+             InvisibleCharProcessor, InvisibleCategory, InvisibleAction, BulletListProcessor,
+             NumberedListProcessor, AlignedTableProcessor, TableStitchProcessor,
+             TableCaptionProcessor, HtmlTableProcessor, ProcessContext, TableFormat,
+             extract_tables, write_table_exports, extract_images, TableLinearizeProcessor, TableLinearization,
+             ImageAssetProcessor, ImageCheckProcessor,
+             DataUriImageProcessor, RepeatedImageProcessor, FigureCaptionProcessor, CjkSpacingProcessor, SpacingPolicy,
+             PunctuationProcessor, PunctuationStyle, RubyProcessor, RubyOutput, VerticalTextProcessor};
 
 /// Helper function to create a cleaner with all processors
 fn create_cleaner() -> MarkdownCleaner {
@@ -153,4 +161,739 @@ fn test_edge_cases() {
     let table_only = "| A | B |\n|---|---|\n| 1 | 2 |";
     let result = cleaner.clean(table_only).expect("Processing table-only content should succeed");
     assert!(result.contains("| A | B |"), "Table-only content should preserve table structure");
+}
+
+#[test]
+fn test_ligature_expansion() {
+    let mut cleaner = create_cleaner();
+    cleaner.add_processor(Arc::new(LigatureProcessor::new()));
+
+    let input = "The \u{FB01}rst e\u{FB03}cient \u{FB02}ow\n\n```\nco\u{FB01}g\n```\n\n\u{FE35}縦書き\u{FE36}";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+
+    assert_eq!(result, "The first efficient flow\n\n```\nco\u{FB01}g\n```\n\n（縦書き）",
+               "Ligatures should be expanded outside code blocks only");
+    assert_eq!(report.count("LigatureProcessor", "expanded"), 5,
+               "Report should count every expanded ligature");
+
+    // Custom table: keep ﬁ, expand a user-defined character
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(LigatureProcessor::new()
+        .without_mapping('\u{FB01}')
+        .with_mapping('\u{2116}', "No.")));
+    let result = cleaner.clean("\u{FB01} \u{2116}1").expect("Processing should succeed");
+    assert_eq!(result, "\u{FB01} No.1", "Custom ligature table not applied");
+}
+
+#[test]
+fn test_unicode_normalization_policies() {
+    let input = "ＡＢＣ１２３と\u{FF76}\u{FF80}\u{FF76}\u{FF85}\u{FF8C}\u{FF9E}\u{FF9B}\u{FF70}\u{FF8F}\u{FF9F}、か\u{3099}いこ\u{3099}く";
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(NormalizationProcessor::new()));
+    let result = cleaner.clean(input).expect("Processing should succeed");
+    assert_eq!(result, "ＡＢＣ１２３と\u{FF76}\u{FF80}\u{FF76}\u{FF85}\u{FF8C}\u{FF9E}\u{FF9B}\u{FF70}\u{FF8F}\u{FF9F}、がいごく",
+               "NFC should only compose decomposed characters");
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(NormalizationProcessor::with_policies(&[
+        NormalizationPolicy::FullwidthAsciiToHalfwidth,
+        NormalizationPolicy::HalfwidthKatakanaToFullwidth,
+    ])));
+    let result = cleaner.clean(input).expect("Processing should succeed");
+    assert_eq!(result, "ABC123とカタカナブローマ゜、がいごく",
+               "Width conversion policies not applied");
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(NormalizationProcessor::with_policies(&[NormalizationPolicy::Nfkc])));
+    let (result, report) = cleaner.clean_with_report("①ＡＢＣ㈱\n\n`ＡＢＣ` and $ｘ^２$")
+        .expect("Processing should succeed");
+    assert_eq!(result, "1ABC(株)\n\n`ＡＢＣ` and $ｘ^２$",
+               "NFKC should skip inline code and math");
+    assert_eq!(report.count("NormalizationProcessor", "normalized"), 1);
+
+    // Full Unicode tables: Hangul jamo, Latin extended, CJK compatibility ideographs
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(NormalizationProcessor::new()));
+    let result = cleaner.clean("\u{1112}\u{1161}\u{11AB} G\u{0306} \u{F900}").expect("Processing should succeed");
+    assert_eq!(result, "한 Ğ \u{8C48}");
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(NormalizationProcessor::with_policies(&[NormalizationPolicy::Nfkc])));
+    let result = cleaner.clean("㍻ ㎏ ½").expect("Processing should succeed");
+    assert_eq!(result, "平成 kg 1\u{2044}2");
+}
+
+#[test]
+fn test_invisible_character_scrubbing() {
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(InvisibleCharProcessor::new()));
+
+    let input = "zero\u{200B}width soft\u{AD}hyphen\u{FEFF}bom\u{0007}bell\n\n文字化け\u{FFFD}と外字\u{E001}";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+
+    assert_eq!(result, "zerowidth softhyphenbombell\n\n文字化け\u{FFFD}と外字\u{E001}",
+               "Invisible characters should be removed and garbled glyphs kept");
+    assert_eq!(report.count("InvisibleCharProcessor", "removed.zero_width"), 1);
+    assert_eq!(report.count("InvisibleCharProcessor", "removed.control"), 1);
+
+    let flagged: Vec<_> = report.entries_for("InvisibleCharProcessor").collect();
+    assert_eq!(flagged.len(), 2, "U+FFFD and PUA hits should be reported");
+    let position = flagged[0].position.as_ref().expect("Flagged hits should carry a position");
+    // Columns count bytes, as in markdown-rs positions
+    assert_eq!((position.start.line, position.start.column), (3, 13));
+
+    // Continuation lines inside a blockquote keep their `> ` prefix in the column
+    let (_, report) = cleaner.clean_with_report("> 一行目\n> 日本語\u{FFFD}です").expect("Processing should succeed");
+    let entry = report.entries_for("InvisibleCharProcessor").next().expect("flagged entry");
+    let position = entry.position.as_ref().expect("position");
+    assert_eq!((position.start.line, position.start.column, position.start.offset), (2, 12, 23));
+
+    // Per-category configuration
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(InvisibleCharProcessor::new()
+        .with_action(InvisibleCategory::SoftHyphen, InvisibleAction::Keep)
+        .with_action(InvisibleCategory::ReplacementCharacter, InvisibleAction::Remove)));
+    let result = cleaner.clean("soft\u{AD}hyphen\u{FFFD}").expect("Processing should succeed");
+    assert_eq!(result, "soft\u{AD}hyphen", "Category actions not applied");
+
+    // Emoji ZWJ sequences and RTL marks survive the defaults
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(InvisibleCharProcessor::new()));
+    let input = "family 👨\u{200D}👩\u{200D}👧 and שלום\u{200F} (עברית)\u{200F} می\u{200C}خواهم";
+    let result = cleaner.clean(input).expect("Processing should succeed");
+    assert_eq!(result, input, "Joiners and bidi marks should be kept by default");
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(InvisibleCharProcessor::new()
+        .with_action(InvisibleCategory::JoinerOrBidi, InvisibleAction::Remove)));
+    let result = cleaner.clean("a\u{200D}b\u{202E}c").expect("Processing should succeed");
+    assert_eq!(result, "abc");
+}
+
+#[test]
+fn test_pseudo_bullet_list_conversion() {
+    let mut cleaner = create_cleaner();
+    cleaner.add_processor(Arc::new(BulletListProcessor::new()));
+
+    let input = "以下の　　　項目：\n• 第一項目\n◦ 子項目　　　です\n◦ 子項目2\n• 第二項目\n\n## 見出し\n\n●段落の箇条書き\n\n●次の段落\n続きの行\n\n-5度の気温と -glued text\n\n・単独";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+
+    let expected = "以下の項目：\n\n- 第一項目\n  - 子項目です\n  - 子項目2\n- 第二項目\n\n## 見出し\n\n- 段落の箇条書き\n- 次の段落続きの行\n\n-5度の気温と -glued text\n\n・単独";
+    assert_eq!(result, expected, "Bullet runs should become nested lists");
+    assert_eq!(report.count("BulletListProcessor", "lists"), 2);
+    assert_eq!(report.count("BulletListProcessor", "items"), 6);
+}
+
+#[test]
+fn test_numbered_list_detection() {
+    let mut cleaner = create_cleaner();
+    cleaner.add_processor(Arc::new(NumberedListProcessor::new()));
+
+    let input = "手順：\n(3) 電源を入れる\n(4) 設定を開く\n\n①準備\n②実行\n\n一、総則\n二、目的\n\na. first\nb. second\n\n1.5倍の速度です";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+
+    let expected = "手順：\n\n3. 電源を入れる\n4. 設定を開く\n\n1. 準備\n2. 実行\n\n1. 総則\n2. 目的\n\n1. first\n2. second\n\n1.5倍の速度です";
+    assert_eq!(result, expected, "Enumerated runs should become ordered lists");
+    assert_eq!(report.count("NumberedListProcessor", "lists"), 4);
+
+    // Non-consecutive numbers are not a list
+    let result = cleaner.clean("(1) 前\n(5) 後").expect("Processing should succeed");
+    assert_eq!(result, "(1) 前 (5) 後");
+
+    // Lines left over after a run stay ahead of the following paragraph
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(NumberedListProcessor::new()));
+    let result = cleaner.clean("(1) a\n(2) b\n(5) c\n\nNext paragraph").expect("Processing should succeed");
+    assert_eq!(result, "1. a\n2. b\n\n(5) c\n\nNext paragraph");
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(NumberedListProcessor::new().with_preserve_markers(true)));
+    let result = cleaner.clean("①準備\n②実行").expect("Processing should succeed");
+    assert_eq!(result, "1. ① 準備\n2. ② 実行", "Original markers should be preserved in an ordered list");
+}
+
+#[test]
+fn test_aligned_pseudo_table_conversion() {
+    let mut cleaner = create_cleaner();
+    cleaner.add_processor(Arc::new(AlignedTableProcessor::new()));
+
+    let input = "売上の　　　一覧です。\n商品名      数量    価格\nりんご        10       1\nみかん　　　　 5   12000\n\n普通の  段落です。\n二行目  です。";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+
+    let expected = "売上の一覧です。\n\n| 商品名 | 数量 | 価格 |\n| --- | --- | ---: |\n| りんご | 10 | 1 |\n| みかん | 5 | 12000 |\n\n普通の 段落です。二行目 です。";
+    assert_eq!(result, expected, "Aligned columns should become a GFM table");
+    assert_eq!(report.count("AlignedTableProcessor", "tables"), 1);
+
+    // A single ideographic space separates words, not columns
+    let headings = "第一条　総則\n第二条　目的\n第三条　定義";
+    let (result, report) = cleaner.clean_with_report(headings).expect("Processing should succeed");
+    assert!(!result.contains('|'), "Statute headings should not become a table: {}", result);
+    assert_eq!(report.count("AlignedTableProcessor", "tables"), 0);
+}
+
+#[test]
+fn test_malformed_pipe_table_repair() {
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(TableProcessor::new().with_repair(true)));
+    cleaner.add_processor(Arc::new(WhitespaceProcessor::new()));
+
+    // No delimiter row, one short row and one row with an overflow cell
+    let input = "| 名前 | 数量 | 備考 |\n| りんご | 10 |\n| みかん | 5 | box | only |\n| ぶどう | 3 | ![ぶどう](grape.jpg) |";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+
+    let expected = "| 名前 | 数量 | 備考 |\n| --- | --- | --- |\n| りんご | 10 |  |\n| みかん | 5 | box only |\n| ぶどう | 3 | ![ぶどう](grape.jpg) |";
+    assert_eq!(result, expected, "Malformed table should be repaired");
+    assert_eq!(report.count("TableProcessor", "delimiter_rows_synthesized"), 1);
+    assert_eq!(report.count("TableProcessor", "rows_padded"), 1);
+    assert_eq!(report.count("TableProcessor", "rows_merged"), 1);
+    assert_eq!(report.entries_for("TableProcessor").count(), 3, "Each repair should be reported");
+
+    // Well-formed tables are preserved exactly
+    let input = include_str!("fixtures/with_tables.md");
+    let expected = include_str!("expected/with_tables_cleaned.md");
+    let result = cleaner.clean(input).expect("Processing should succeed");
+    assert_eq!(result.trim(), expected.trim(), "Valid tables should not be rewritten");
+
+    // Adjacent tables stay separate
+    let input = "| name | qty |\n| --- | --- |\n| x | 3 |\n\n| name | price |\n| --- | --- |\n| x | 9 |";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+    assert_eq!(result, input, "Adjacent well-formed tables should not be merged");
+    assert_eq!(report.count("TableProcessor", "tables_repaired"), 0);
+
+    // Rows of bare pipes do not crash the repair
+    let result = cleaner.clean("|\n|\n| a | b |").expect("Processing should succeed");
+    assert_eq!(result, "| a | b |\n| --- | --- |");
+
+    // Rows of dashes after the second row are data, not misplaced delimiter rows
+    let result = cleaner.clean("| 項目 | 値 |\n| 変更 | - |\n| - | - |").expect("Processing should succeed");
+    assert_eq!(result, "| 項目 | 値 |\n| --- | --- |\n| 変更 | - |\n| - | - |");
+
+    // Paragraphs with nothing to repair keep their source position
+    let input = "| a | b |\n| --- | --- |\n| - | - |";
+    let (root, report) = cleaner.process_tree(input, ProcessContext::default()).expect("Processing should succeed");
+    match root.children().and_then(|children| children.first()) {
+        Some(markdown::mdast::Node::Paragraph(paragraph)) => assert!(paragraph.position.is_some()),
+        other => panic!("Expected the original paragraph, got {:?}", other),
+    }
+    assert_eq!(report.count("TableProcessor", "tables_repaired"), 0);
+}
+
+#[test]
+fn test_table_stitching_across_page_breaks() {
+    let mut cleaner = create_cleaner();
+    cleaner.add_processor(Arc::new(TableStitchProcessor::new()));
+
+    let input = "| 品名 | 価格 |\n|---|---|\n| りんご | 100 |\n\n- 12 -\n\n---\n\n| 品名 | 価格 |\n|---|---|\n| みかん | 80 |\n\n| 品名 | 数量 | 備考 |\n|---|---|---|\n| ぶどう | 1 | 箱 |";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+
+    let expected = "| 品名 | 価格 |\n| --- | --- |\n| りんご | 100 |\n| みかん | 80 |\n\n| 品名 | 数量 | 備考 |\n|---|---|---|\n| ぶどう | 1 | 箱 |";
+    assert_eq!(result, expected, "Split table should be merged without the repeated header");
+    assert_eq!(report.count("TableStitchProcessor", "tables_stitched"), 1);
+    assert_eq!(report.count("TableStitchProcessor", "page_artifacts_dropped"), 2);
+
+    // Unrelated tables of the same width are not stitched without a page break
+    let input = "| 品名 | 価格 |\n|---|---|\n| りんご | 100 |\n\n| 都市 | 人口 |\n|---|---|\n| 東京 | 1400 |";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+    assert_eq!(result, input);
+    assert_eq!(report.count("TableStitchProcessor", "tables_stitched"), 0);
+
+    // A thematic break or a bare number between them is not enough either
+    for separator in ["---", "12"] {
+        let input = format!("| 品名 | 価格 |\n|---|---|\n| りんご | 100 |\n\n{}\n\n| 都市 | 人口 |\n|---|---|\n| 東京 | 1400 |", separator);
+        let (result, report) = cleaner.clean_with_report(&input).expect("Processing should succeed");
+        assert!(result.contains("| 都市 | 人口 |\n|---|---|"), "Tables separated by {:?} should stay apart: {}", separator, result);
+        assert_eq!(report.count("TableStitchProcessor", "tables_stitched"), 0);
+    }
+
+    // Rows continuing without a header are appended
+    let input = "| 品名 | 価格 |\n|---|---|\n| りんご | 100 |\n\n| みかん | 80 |\n| ぶどう | 300 |";
+    let result = cleaner.clean(input).expect("Processing should succeed");
+    assert_eq!(result, "| 品名 | 価格 |\n| --- | --- |\n| りんご | 100 |\n| みかん | 80 |\n| ぶどう | 300 |");
+}
+
+#[test]
+fn test_table_caption_placement() {
+    let mut cleaner = create_cleaner();
+    cleaner.add_processor(Arc::new(TableCaptionProcessor::new()));
+
+    // Caption below its table, caption glued to the table lines, and body text mentioning a table
+    let input = "| 月 | 売上 |\n|---|---|\n| 4月 | 100 |\n\n表1：売上一覧\n\nTable 2: Results\n| a | b |\n|---|---|\n| 1 | 2 |\n\n表3は参考資料です。";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+
+    let expected = "表1：売上一覧\n\n| 月 | 売上 |\n|---|---|\n| 4月 | 100 |\n\nTable 2: Results\n\n| a | b |\n|---|---|\n| 1 | 2 |\n\n表3は参考資料です。";
+    assert_eq!(result, expected, "Captions should be placed above their tables");
+    assert_eq!(report.count("TableCaptionProcessor", "captions"), 2);
+    assert_eq!(report.count("TableCaptionProcessor", "moved_above"), 1);
+
+    assert!(report.entries_for("TableCaptionProcessor").any(|entry| entry.message == "table 1: caption \"表1：売上一覧\" moved above the table"));
+    assert!(report.entries_for("TableCaptionProcessor").any(|entry| entry.message == "table 2: caption \"Table 2: Results\""));
+
+    // English body prose starting with a table reference is not a caption
+    let input = "| a | b |\n|---|---|\n| 1 | 2 |\n\nTable 3 shows the results of the survey.";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+    assert_eq!(result, input);
+    assert_eq!(report.count("TableCaptionProcessor", "captions"), 0);
+}
+
+#[test]
+fn test_html_table_conversion() {
+    let mut cleaner = create_cleaner();
+    cleaner.add_processor(Arc::new(HtmlTableProcessor::new()));
+
+    let input = "<table>\n<thead><tr><th>地域</th><th colspan=\"2\">売上</th></tr></thead>\n<tbody>\n<tr><td rowspan=\"2\">東京</td><td>4月</td><td align=\"right\">1,200</td></tr>\n<tr><td>5月</td><td>980 &amp; more<br>units</td></tr>\n</tbody>\n</table>\n\n<table><tr><td><ul><li>item</li></ul></td></tr></table>";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+
+    let expected = "| 地域 | 売上 | 売上 |\n| --- | --- | --- |\n| 東京 | 4月 | 1,200 |\n| 東京 | 5月 | 980 & more units |\n\n<table><tr><td><ul><li>item</li></ul></td></tr></table>";
+    assert_eq!(result, expected, "Simple HTML table should become a GFM table with spans expanded");
+    assert_eq!(report.count("HtmlTableProcessor", "tables_converted"), 1);
+    assert_eq!(report.count("HtmlTableProcessor", "spans_expanded"), 2);
+    assert_eq!(report.count("HtmlTableProcessor", "tables_skipped"), 1);
+    assert!(report.entries_for("HtmlTableProcessor")
+        .any(|entry| entry.message == "left HTML table intact: unsupported element <ul>"));
+}
+
+#[test]
+fn test_table_export_sidecar_files() {
+    let cleaner = create_cleaner();
+    let input = "表1：価格表\n\n| 品名 | 価格 |\n|---|---|\n| りんご, 赤 | 100 |\n| \"特選\"みかん | 80 |";
+    let (root, _) = cleaner.process_tree(input, ProcessContext::default()).expect("Processing should succeed");
+
+    let tables = extract_tables(&root);
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].caption.as_deref(), Some("表1：価格表"));
+    assert_eq!(tables[0].position.as_ref().map(|position| position.start.line), Some(3));
+    assert_eq!(TableFormat::Csv.render(&tables[0]), "品名,価格\r\n\"りんご, 赤\",100\r\n\"\"\"特選\"\"みかん\",80\r\n");
+    assert_eq!(TableFormat::Tsv.render(&tables[0]), "品名\t価格\nりんご, 赤\t100\n\"特選\"みかん\t80\n");
+
+    let directory = std::env::temp_dir().join(format!("mdclean-export-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).expect("temp dir");
+    let base = directory.join("out.md");
+    let written = write_table_exports(&base, &tables, TableFormat::Json).expect("Export should succeed");
+
+    assert_eq!(written, vec![directory.join("out.md.table-1.json"), directory.join("out.md.tables.json")]);
+    let json = std::fs::read_to_string(&written[0]).expect("table file");
+    assert!(json.contains("\"header\": [\"品名\", \"価格\"]"));
+    let manifest = std::fs::read_to_string(&written[1]).expect("manifest");
+    assert!(manifest.contains("\"file\": \"out.md.table-1.json\", \"caption\": \"表1：価格表\", \"line\": 3, \"column\": 1"));
+    std::fs::remove_dir_all(&directory).ok();
+}
+
+#[test]
+fn test_table_linearization_forms() {
+    let input = "| 品名 | 価格 |\n|---|---|\n| りんご | 100 |\n| みかん |  |\n\n| Name | Price |\n|---|---|\n| apple | 100 |";
+    let linearize = |style| {
+        let mut cleaner = create_cleaner();
+        cleaner.add_processor(Arc::new(TableLinearizeProcessor::new(style)));
+        cleaner.clean_with_report(input).expect("Processing should succeed")
+    };
+
+    let (key_value, report) = linearize(TableLinearization::KeyValue);
+    assert_eq!(key_value, "- 品名: りんご; 価格: 100\n- 品名: みかん\n\n- Name: apple; Price: 100");
+    assert_eq!(report.count("TableLinearizeProcessor", "tables"), 2);
+    assert_eq!(report.count("TableLinearizeProcessor", "rows"), 3);
+
+    let (tsv, _) = linearize(TableLinearization::Tsv);
+    assert_eq!(tsv, "```tsv\n品名\t価格\nりんご\t100\nみかん\t\n```\n\n```tsv\nName\tPrice\napple\t100\n```");
+
+    let (sentence, _) = linearize(TableLinearization::Sentence);
+    assert_eq!(sentence, "品名はりんご、価格は100。品名はみかん。\n\nName is apple, Price is 100.");
+}
+
+#[test]
+fn test_image_normalization() {
+    let input = "![  画像   説明 ](  images\\figure.png  \"図 1\")\n\n本文 ![chart](my chart.png) です。\n\n![x](a%2520b.png) ![y](100%.png)";
+
+    // Default stays exact (apart from the title, which is now kept)
+    let (preserved, _) = create_cleaner().clean_with_report(input).expect("Processing should succeed");
+    assert!(preserved.starts_with("![  画像   説明 ](images\\figure.png \"図 1\")"));
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(ImageProcessor::new().with_normalization(true)));
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+
+    let expected = "![画像 説明](images/figure.png \"図 1\")\n\n本文 ![chart](my%20chart.png) です。\n\n![x](a%20b.png) ![y](100%25.png)";
+    assert_eq!(result, expected, "Alt text and URLs should be normalized");
+    assert_eq!(report.count("ImageProcessor", "alt_normalized"), 1);
+    assert_eq!(report.count("ImageProcessor", "url_normalized"), 4);
+    assert_eq!(report.count("ImageProcessor", "recovered"), 1);
+}
+
+#[test]
+fn test_image_rebasing_and_asset_copying() {
+    let root = std::env::temp_dir().join(format!("mdclean-assets-{}", std::process::id()));
+    let input_dir = root.join("input");
+    std::fs::create_dir_all(input_dir.join("img")).expect("temp dir");
+    std::fs::write(input_dir.join("img/chart 1.png"), b"png").expect("image");
+    let context = || ProcessContext {
+        file_path: Some(input_dir.join("doc.md").to_string_lossy().into_owned()),
+        ..ProcessContext::default()
+    };
+    let input = "![グラフ](img/chart%201.png)\n\n![missing](img/none.png)\n\n![remote](https://example.com/a.png)";
+
+    // Rebase onto another output directory without copying
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(ImageAssetProcessor::new().with_output_dir(root.join("out/docs"))));
+    let (result, _) = cleaner.clean_with_context(input, context()).expect("Processing should succeed");
+    assert_eq!(result, "![グラフ](../../input/img/chart%201.png)\n\n![missing](../../input/img/none.png)\n\n![remote](https://example.com/a.png)");
+
+    // Copy into an assets directory served under a prefix
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(ImageAssetProcessor::new()
+        .with_assets_dir(root.join("out/assets"))
+        .with_asset_prefix("https://cdn.example.com/assets/")));
+    let (result, report) = cleaner.clean_with_context(input, context()).expect("Processing should succeed");
+    assert_eq!(result, "![グラフ](https://cdn.example.com/assets/chart%201.png)\n\n![missing](img/none.png)\n\n![remote](https://example.com/a.png)");
+    assert!(root.join("out/assets/chart 1.png").is_file(), "Referenced image should be copied");
+    assert_eq!(report.count("ImageAssetProcessor", "copied"), 1);
+    assert_eq!(report.count("ImageAssetProcessor", "missing"), 1);
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[test]
+fn test_cli_copies_assets_relative_to_output() {
+    let root = std::env::temp_dir().join(format!("mdclean-cli-assets-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("input/img")).expect("temp dir");
+    std::fs::create_dir_all(root.join("out/docs")).expect("temp dir");
+    std::fs::write(root.join("input/img/chart.png"), b"png").expect("image");
+    std::fs::write(root.join("input/doc.md"), "![グラフ](img/chart.png)").expect("input");
+
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_mdclean"))
+        .arg("--assets-dir")
+        .arg(root.join("out/assets"))
+        .arg(root.join("input/doc.md"))
+        .arg(root.join("out/docs/doc.md"))
+        .status()
+        .expect("mdclean should run");
+    assert!(status.success());
+
+    // The copy is linked from the output file, not from the input directory
+    let output = std::fs::read_to_string(root.join("out/docs/doc.md")).expect("output");
+    assert_eq!(output.trim(), "![グラフ](../assets/chart.png)");
+    assert!(root.join("out/assets/chart.png").is_file(), "Referenced image should be copied");
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[test]
+fn test_missing_image_verification() {
+    let root = std::env::temp_dir().join(format!("mdclean-check-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("img")).expect("temp dir");
+    std::fs::write(root.join("img/ok.png"), b"png").expect("image");
+    let context = || ProcessContext {
+        file_path: Some(root.join("doc.md").to_string_lossy().into_owned()),
+        ..ProcessContext::default()
+    };
+    let input = "# 図\n\n![ok](img/ok.png)\n\nSee ![lost](img/lost.png)\n\n![remote](https://example.com/a.png)";
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(ImageCheckProcessor::new()));
+    let (result, report) = cleaner.clean_with_context(input, context()).expect("Non-strict check should not fail");
+    assert_eq!(result, input);
+    assert_eq!(report.count("ImageCheckProcessor", "checked"), 2);
+    assert_eq!(report.count("ImageCheckProcessor", "missing"), 1);
+    let entry = report.entries_for("ImageCheckProcessor").next().expect("missing image entry");
+    assert_eq!(entry.message, "missing image: img/lost.png");
+    assert_eq!(entry.position.as_ref().map(|p| (p.start.line, p.start.column)), Some((5, 5)));
+
+    let mut strict = MarkdownCleaner::new();
+    strict.add_processor(Arc::new(ImageCheckProcessor::new().with_strict(true)));
+    let error = strict.clean_with_context(input, context()).expect_err("Strict check should fail");
+    assert_eq!(error.to_string(), "1 missing image(s): img/lost.png (5:5)");
+
+    // Absolute paths and file URLs are checked too
+    let absolute = root.join("img/ok.png").to_string_lossy().into_owned();
+    let lost = root.join("img/lost.png").to_string_lossy().into_owned();
+    let input = format!("![abs]({})\n\n![url](file://{})\n\n![win](C:\\x.png)", absolute, lost);
+    let (_, report) = cleaner.clean_with_context(&input, context()).expect("Non-strict check should not fail");
+    assert_eq!(report.count("ImageCheckProcessor", "checked"), 2);
+    assert_eq!(report.count("ImageCheckProcessor", "missing"), 1);
+    if !cfg!(windows) {
+        assert_eq!(report.count("ImageCheckProcessor", "unchecked"), 1);
+    }
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[test]
+fn test_data_uri_image_extraction() {
+    let root = std::env::temp_dir().join(format!("mdclean-datauri-{}", std::process::id()));
+    let context = || ProcessContext {
+        file_path: Some(root.join("doc.md").to_string_lossy().into_owned()),
+        ..ProcessContext::default()
+    };
+    // "GIF89a" twice (deduplicated), an SVG in percent-encoded form, and a non-image
+    let input = "![a](data:image/gif;base64,R0lGODlh)\n\n![b](data:image/gif;base64,R0lG%0AODlh)\n\n![c](data:image/svg+xml,%3Csvg%2F%3E)\n\n![d](data:text/plain;base64,aGk=)";
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(DataUriImageProcessor::new(root.join("assets"))));
+    let (result, report) = cleaner.clean_with_context(input, context()).expect("Processing should succeed");
+
+    let lines: Vec<&str> = result.split("\n\n").collect();
+    assert_eq!(lines[0], lines[1].replacen("![b]", "![a]", 1), "Identical images should share one file");
+    let gif = lines[0].trim_start_matches("![a](").trim_end_matches(')');
+    assert!(gif.starts_with("assets/image-") && gif.ends_with(".gif"));
+    assert_eq!(std::fs::read(root.join(gif)).expect("extracted gif"), b"GIF89a");
+    let svg = lines[2].trim_start_matches("![c](").trim_end_matches(')');
+    assert_eq!(std::fs::read_to_string(root.join(svg)).expect("extracted svg"), "<svg/>");
+    assert_eq!(lines[3], "![d](data:text/plain;base64,aGk=)");
+
+    assert_eq!(report.count("DataUriImageProcessor", "extracted"), 2);
+    assert_eq!(report.count("DataUriImageProcessor", "deduplicated"), 1);
+    assert_eq!(report.count("DataUriImageProcessor", "invalid"), 1);
+
+    // A different file already under the hashed name is kept and the image gets a new name
+    std::fs::write(root.join(gif), b"other").expect("clashing file");
+    let (result, report) = cleaner.clean_with_context("![a](data:image/gif;base64,R0lGODlh)", context()).expect("Processing should succeed");
+    let renamed = result.trim_start_matches("![a](").trim_end_matches(')');
+    assert_eq!(renamed, gif.replace(".gif", "-1.gif"));
+    assert_eq!(std::fs::read(root.join(renamed)).expect("renamed gif"), b"GIF89a");
+    assert_eq!(std::fs::read(root.join(gif)).expect("clashing file"), b"other");
+    assert_eq!(report.count("DataUriImageProcessor", "deduplicated"), 0);
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[test]
+fn test_repeated_decorative_image_removal() {
+    let input = "![logo](logo.png)\n\n本文1\n\n![図1](fig1.png)\n\n![logo](logo.png)\n\n本文2 ![logo](logo.png)\n\n![seal](seal.png)\n\n![seal](seal.png)\n\n![seal](seal.png)";
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(RepeatedImageProcessor::new().with_allowed("seal")));
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+    assert_eq!(result, "![logo](logo.png)\n\n本文1\n\n![図1](fig1.png)\n\n本文2 \n\n![seal](seal.png)\n\n![seal](seal.png)\n\n![seal](seal.png)");
+    assert_eq!(report.count("RepeatedImageProcessor", "removed"), 2);
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(RepeatedImageProcessor::new().with_keep_first(false).with_threshold(2)));
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+    assert_eq!(result, "本文1\n\n![図1](fig1.png)\n\n本文2 ");
+    assert_eq!(report.count("RepeatedImageProcessor", "removed"), 6);
+
+    // Images occurring exactly `threshold` times are kept
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(RepeatedImageProcessor::new().with_threshold(3)));
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+    assert_eq!(result, input);
+    assert_eq!(report.count("RepeatedImageProcessor", "removed"), 0);
+}
+
+#[test]
+fn test_figure_caption_linking() {
+    let input = "![](arch.png)\n\n図1：システム構成\n\nFigure 2: Setup\n\n![](setup.png)\n\n![kept](flow.png)\n図3 処理の流れ\n\n図4を参照してください。本文がここに続きます。";
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(FigureCaptionProcessor::new()));
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+    assert!(result.starts_with("![](arch.png)"));
+    assert_eq!(report.count("FigureCaptionProcessor", "captions"), 3);
+    assert_eq!(report.count("FigureCaptionProcessor", "alt_filled"), 0);
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(FigureCaptionProcessor::new().with_fill_alt(true)));
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+    assert!(result.contains("![図1：システム構成](arch.png)"));
+    assert!(result.contains("![Figure 2: Setup](setup.png)"));
+    assert!(result.contains("![kept](flow.png)"));
+    assert_eq!(report.count("FigureCaptionProcessor", "alt_filled"), 2);
+    assert!(report.entries_for("FigureCaptionProcessor").any(|entry| entry.message.contains("`setup.png`")));
+}
+
+#[test]
+fn test_image_manifest() {
+    let input = "# 概要\n\n## 構成\n\n![](arch.png \"Architecture\")\n\n図1：システム構成\n\n# 付録\n\n本文 ![icon](icon.png) を参照";
+
+    let cleaner = create_cleaner();
+    let (root, _) = cleaner.process_tree(input, ProcessContext::default()).expect("Processing should succeed");
+    let images = extract_images(&root);
+    assert_eq!(images.len(), 2);
+    assert_eq!(images[0].url, "arch.png");
+    assert_eq!(images[0].title.as_deref(), Some("Architecture"));
+    assert_eq!(images[0].caption.as_deref(), Some("図1：システム構成"));
+    assert_eq!(images[0].headings, vec!["概要", "構成"]);
+    assert_eq!(images[0].position.as_ref().map(|position| position.start.line), Some(5));
+    assert_eq!(images[1].alt, "icon");
+    assert_eq!(images[1].caption, None);
+    assert_eq!(images[1].headings, vec!["付録"]);
+
+    let manifest = mdclean::export::image_manifest(&images);
+    assert!(manifest.contains("\"url\": \"arch.png\", \"alt\": \"\", \"title\": \"Architecture\", \"line\": 5"));
+    assert!(manifest.contains("\"headings\": [\"概要\", \"構成\"]"));
+
+    // A caption is paired with one image only, as in FigureCaptionProcessor
+    let (root, _) = cleaner.process_tree("![a](a.png)\n\n図1：構成\n\n![b](b.png)", ProcessContext::default())
+        .expect("Processing should succeed");
+    let captions: Vec<_> = extract_images(&root).into_iter().map(|image| image.caption).collect();
+    assert_eq!(captions, vec![Some("図1：構成".to_string()), None]);
+
+    // Images outside paragraphs: headings, table cells, list items, links
+    let input = "# Title ![logo](h.png)\n\n| a | b |\n| ![cell](c.png) | x |\n\n- ![li](li.png)\n\n[![badge](b.svg)](https://example.com)";
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(TableProcessor::new().with_repair(true)));
+    let (root, _) = cleaner.process_tree(input, ProcessContext::default()).expect("Processing should succeed");
+    let images = extract_images(&root);
+    let urls: Vec<&str> = images.iter().map(|image| image.url.as_str()).collect();
+    assert_eq!(urls, vec!["h.png", "c.png", "li.png", "b.svg"]);
+    assert_eq!(images[0].headings, vec!["Title"]);
+    assert!(matches!(&root.children().expect("root")[1], markdown::mdast::Node::Table(_)), "Repaired table should hold the cell image");
+}
+
+#[test]
+fn test_whitespace_character_classes() {
+    let clean = |processor: WhitespaceProcessor, input: &str| {
+        let mut cleaner = MarkdownCleaner::new();
+        cleaner.add_processor(Arc::new(processor));
+        cleaner.clean(input).expect("Processing should succeed")
+    };
+
+    // Digits join CJK text, but digit groups keep their spaces
+    assert_eq!(clean(WhitespaceProcessor::new(), "価格　1 000 円、表1 売上"), "価格1 000円、表1売上");
+    assert_eq!(clean(WhitespaceProcessor::new().with_class(CharClass::Digits, false), "合計 1 000 円"), "合計 1 000 円");
+
+    // Chinese: extension A, compatibility ideographs and bopomofo
+    let chinese = WhitespaceProcessor::with_preset(LanguagePreset::Chinese);
+    assert_eq!(clean(chinese, "这是　　一个 测试。 㐀 豈 ㄅㄆ ㄇ"), "这是一个测试。㐀豈ㄅㄆㄇ");
+
+    // Korean words stay separated; runs of spaces collapse and hanja is joined
+    let korean = WhitespaceProcessor::with_preset(LanguagePreset::Korean);
+    assert_eq!(clean(korean, "안녕하세요　　세계 漢 字"), "안녕하세요 세계 漢字");
+    let letter_spaced = WhitespaceProcessor::with_preset(LanguagePreset::Korean).with_class(CharClass::Hangul, true);
+    assert_eq!(clean(letter_spaced, "한 국 어"), "한국어");
+
+    // Custom ranges
+    assert_eq!(clean(WhitespaceProcessor::new().with_range('α'..='ω'), "α β γ"), "αβγ");
+}
+
+#[test]
+fn test_cjk_latin_spacing_policies() {
+    let input = "Rustの実装とRust の実装\n\n`cargo build`を使う、使う `cargo` コマンド\n\n詳細はhttps://example.com/docsを参照、合計100件";
+    let clean = |policy: SpacingPolicy| {
+        let mut cleaner = MarkdownCleaner::new();
+        cleaner.add_processor(Arc::new(ImageProcessor::new()));
+        cleaner.add_processor(Arc::new(TableProcessor::new()));
+        cleaner.add_processor(Arc::new(WhitespaceProcessor::new().with_inline_edges(true)));
+        cleaner.add_processor(Arc::new(CjkSpacingProcessor::new(policy)));
+        cleaner.clean_with_report(input).expect("Processing should succeed")
+    };
+
+    let (result, report) = clean(SpacingPolicy::Insert);
+    assert_eq!(result, "Rust の実装と Rust の実装\n\n`cargo build` を使う、使う `cargo` コマンド\n\n詳細はhttps://example.com/docsを参照、合計 100 件");
+    assert!(report.count("CjkSpacingProcessor", "inserted") >= 5);
+
+    let (result, report) = clean(SpacingPolicy::Remove);
+    assert_eq!(result, "Rustの実装とRustの実装\n\n`cargo build`を使う、使う`cargo`コマンド\n\n詳細はhttps://example.com/docsを参照、合計100件");
+    assert_eq!(report.count("CjkSpacingProcessor", "removed"), 3);
+
+    let (result, _) = clean(SpacingPolicy::Preserve);
+    assert_eq!(result, create_cleaner().clean(input).expect("Processing should succeed"));
+}
+
+#[test]
+fn test_japanese_punctuation_styles() {
+    let input = "これは，テスト．関数(引数)とf(x)、（注）10~20時、説明——補足。円周率は３．１４、詳細はhttps://example.com/a~b,c.";
+    let clean = |style: PunctuationStyle| {
+        let mut cleaner = MarkdownCleaner::new();
+        cleaner.add_processor(Arc::new(PunctuationProcessor::new(style)));
+        cleaner.clean_with_report(input).expect("Processing should succeed")
+    };
+
+    let (result, report) = clean(PunctuationStyle::Jis);
+    assert_eq!(result, "これは、テスト。関数（引数）とf(x)、（注）10〜20時、説明――補足。円周率は３．１４、詳細はhttps://example.com/a~b,c.");
+    assert_eq!(report.count("PunctuationProcessor", "commas"), 1);
+    assert_eq!(report.count("PunctuationProcessor", "parentheses"), 2);
+    assert_eq!(report.count("PunctuationProcessor", "wave_dashes"), 1);
+    assert_eq!(report.count("PunctuationProcessor", "dashes"), 2);
+
+    let (result, _) = clean(PunctuationStyle::Academic);
+    assert_eq!(result, "これは，テスト．関数（引数）とf(x)，（注）10〜20時，説明――補足．円周率は３．１４，詳細はhttps://example.com/a~b,c.");
+
+    let (result, _) = clean(PunctuationStyle::HalfwidthParentheses);
+    assert_eq!(result, "これは、テスト。関数(引数)とf(x)、(注)10〜20時、説明――補足。円周率は３．１４、詳細はhttps://example.com/a~b,c.");
+
+    // Rule lines of box-drawing dashes are not Japanese dashes
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(PunctuationProcessor::new(PunctuationStyle::Jis)));
+    let input = "第1章\n───\n\n────────\n\n本文";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+    assert_eq!(result, input);
+    assert_eq!(report.count("PunctuationProcessor", "dashes"), 0);
+}
+
+#[test]
+fn test_ruby_artifact_handling() {
+    let input = "漢(かん)字(じ)を書く。\n\n漢字かんじの練習\n\nとう きょう\n東京タワーへいく\n\nこれは例(たとえばこういうの)です。";
+    let clean = |processor: RubyProcessor| {
+        let mut cleaner = MarkdownCleaner::new();
+        cleaner.add_processor(Arc::new(processor));
+        cleaner.clean_with_report(input).expect("Processing should succeed")
+    };
+
+    let (result, report) = clean(RubyProcessor::new(RubyOutput::Strip));
+    assert_eq!(result, "漢字を書く。\n\n漢字の練習\n\n東京タワーへいく\n\nこれは例(たとえばこういうの)です。");
+    assert_eq!(report.count("RubyProcessor", "ruby_lines"), 1);
+    assert_eq!(report.count("RubyProcessor", "annotations"), 5);
+
+    let (result, _) = clean(RubyProcessor::new(RubyOutput::Parentheses));
+    assert_eq!(result, "漢字（かんじ）を書く。\n\n漢字（かんじ）の練習\n\n東京（とうきょう）タワーへいく\n\nこれは例(たとえばこういうの)です。");
+
+    let (result, _) = clean(RubyProcessor::new(RubyOutput::Html).with_reading("練習", "れんしゅう"));
+    assert!(result.starts_with("<ruby>漢<rt>かん</rt>字<rt>じ</rt></ruby>を書く。"));
+    assert!(result.contains("<ruby>漢字<rt>かんじ</rt></ruby>の練習\n"));
+    assert!(result.contains("<ruby>東<rt>とう</rt>京<rt>きょう</rt></ruby>タワー"));
+
+    // Hiragana remarks in parentheses are kept unless the reading is confirmed elsewhere
+    let remarks = "削除(しない)場合もある。\n\n備考(なし)\n\n東京(とうきょう)へ行く。";
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(RubyProcessor::new(RubyOutput::Strip)));
+    let (result, report) = cleaner.clean_with_report(remarks).expect("Processing should succeed");
+    assert_eq!(result, remarks);
+    assert_eq!(report.count("RubyProcessor", "annotations"), 0);
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(RubyProcessor::new(RubyOutput::Strip).with_reading("東京", "とうきょう")));
+    let result = cleaner.clean(remarks).expect("Processing should succeed");
+    assert_eq!(result, "削除(しない)場合もある。\n\n備考(なし)\n\n東京へ行く。");
+
+    // An ordinary kana sentence above a line with kanji is not ruby
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(RubyProcessor::new(RubyOutput::Parentheses)));
+    let input = "そうです\n今日は晴れです。\n\nはい わかりました\n明日は雨です。";
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+    assert_eq!(result, input);
+    assert_eq!(report.count("RubyProcessor", "ruby_lines"), 0);
+}
+
+#[test]
+fn test_vertical_text_reassembly() {
+    let input = "第一章\n吾\n輩\nは\n猫\nで\nあ\nる\n︒\n名\n前\nは\nま\nだ\n無\nい\n。\n\n﹁\n猫\n﹂\n\nで\nす\n\n。\n\n普通の段落です。\n\nこの\n段落\nは\n短い";
+
+    let mut cleaner = MarkdownCleaner::new();
+    cleaner.add_processor(Arc::new(VerticalTextProcessor::new()));
+    let (result, report) = cleaner.clean_with_report(input).expect("Processing should succeed");
+    assert_eq!(result, "第一章\n吾輩は猫である。\n名前はまだ無い。\n\n「猫」です。\n\n普通の段落です。\n\nこの\n段落\nは\n短い");
+    assert_eq!(report.count("VerticalTextProcessor", "runs"), 2);
+    assert_eq!(report.count("VerticalTextProcessor", "lines"), 22);
+
+    // Flowing text once the whitespace processor joins the CJK lines
+    let mut cleaner = create_cleaner();
+    cleaner.add_processor(Arc::new(VerticalTextProcessor::new().with_min_lines(4)));
+    let result = cleaner.clean(input).expect("Processing should succeed");
+    assert_eq!(result, "第一章吾輩は猫である。名前はまだ無い。\n\n「猫」です。\n\n普通の段落です。\n\nこの段落は短い");
+}
+
+#[test]
+fn test_ideographic_indentation() {
+    let input = "　本文の一段落目です。\n　二段落目の文です。\n続きの行。\n\n　　次の段落。\n\nEnglish text\n　インデント";
+    let clean = |processor: WhitespaceProcessor| {
+        let mut cleaner = MarkdownCleaner::new();
+        cleaner.add_processor(Arc::new(processor));
+        cleaner.clean_with_report(input).expect("Processing should succeed")
+    };
+
+    let (result, report) = clean(WhitespaceProcessor::new());
+    assert_eq!(result, "本文の一段落目です。二段落目の文です。続きの行。\n\n次の段落。\n\nEnglish text インデント");
+    assert_eq!(report.count("WhitespaceProcessor", "indents"), 4);
+
+    let (result, _) = clean(WhitespaceProcessor::new().with_keep_indent(true));
+    assert_eq!(result, "　本文の一段落目です。\n　二段落目の文です。続きの行。\n\n　次の段落。\n\nEnglish text\n　インデント");
 }
\ No newline at end of file